@@ -1,6 +1,10 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
 #[must_use]
 pub fn read_totals_from_input(input: &str) -> Vec<u32> {
     let mut elves: Vec<u32> = Vec::new();
+    let input = input.replace("\r\n", "\n");
 
     for carried in input.split("\n\n") {
         let total = carried
@@ -14,25 +18,142 @@ pub fn read_totals_from_input(input: &str) -> Vec<u32> {
     elves
 }
 
+#[derive(Debug, PartialEq)]
+pub struct ParseCaloriesError {
+    line: usize,
+    text: String,
+}
+
+/// # Errors
+///
+/// Will return `Err` identifying the first line which is not a valid calorie count.
+pub fn try_read_totals_from_input(input: &str) -> Result<Vec<u32>, ParseCaloriesError> {
+    let mut elves: Vec<u32> = Vec::new();
+    let mut current: Option<u32> = None;
+
+    for (ix, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            if let Some(total) = current.take() {
+                elves.push(total);
+            }
+        } else {
+            let calories: u32 = line.parse().map_err(|_| ParseCaloriesError {
+                line: ix + 1,
+                text: line.to_string(),
+            })?;
+            current = Some(current.unwrap_or(0) + calories);
+        }
+    }
+
+    if let Some(total) = current {
+        elves.push(total);
+    }
+
+    Ok(elves)
+}
+
 #[must_use]
-pub fn max_total_calories(calories_by_elf: &[u32], quantity: usize) -> Vec<u32> {
+pub fn totals_with_index(input: &str) -> Vec<(usize, u32)> {
+    read_totals_from_input(input)
+        .into_iter()
+        .enumerate()
+        .map(|(ix, total)| (ix + 1, total))
+        .collect()
+}
+
+#[must_use]
+pub fn max_total_calories_with_index(
+    calories_by_elf: &[(usize, u32)],
+    quantity: usize,
+) -> Vec<(usize, u32)> {
     let mut totals = calories_by_elf.to_vec();
-    totals.sort_unstable();
-    totals.reverse();
+    totals.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
     totals.truncate(quantity);
     totals
 }
 
 #[must_use]
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn max_total_calories(calories_by_elf: &[u32], quantity: usize) -> Vec<u32> {
+    let indexed: Vec<(usize, u32)> = calories_by_elf
+        .iter()
+        .enumerate()
+        .map(|(ix, total)| (ix + 1, *total))
+        .collect();
+    max_total_calories_with_index(&indexed, quantity)
+        .into_iter()
+        .map(|(_, total)| total)
+        .collect()
+}
+
+#[must_use]
+pub fn sum_of_top(input: &str, n: usize) -> u32 {
     let totals = read_totals_from_input(input);
-    Some(max_total_calories(&totals, 1).iter().sum())
+    max_total_calories(&totals, n).iter().sum()
+}
+
+fn push_bounded(heap: &mut BinaryHeap<Reverse<u32>>, total: u32, quantity: usize) {
+    if quantity == 0 {
+        return;
+    }
+    if heap.len() < quantity {
+        heap.push(Reverse(total));
+    } else if heap
+        .peek()
+        .is_some_and(|Reverse(smallest)| total > *smallest)
+    {
+        heap.pop();
+        heap.push(Reverse(total));
+    }
+}
+
+#[must_use]
+pub fn streaming_sum_of_top(input: &str, quantity: usize) -> u32 {
+    let mut heap: BinaryHeap<Reverse<u32>> = BinaryHeap::new();
+    let mut current = 0;
+
+    for line in input.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            push_bounded(&mut heap, current, quantity);
+            current = 0;
+        } else {
+            current += line.parse().unwrap_or(0);
+        }
+    }
+    push_bounded(&mut heap, current, quantity);
+
+    heap.iter().map(|Reverse(total)| total).sum()
+}
+
+fn read_totals(input: &str, strict: bool) -> Option<Vec<u32>> {
+    if strict {
+        try_read_totals_from_input(input).ok()
+    } else {
+        Some(read_totals_from_input(input))
+    }
+}
+
+#[must_use]
+pub fn part_one(input: &str) -> Option<u32> {
+    Some(sum_of_top(input, 1))
+}
+
+#[must_use]
+pub fn part_one_strict(input: &str) -> Option<u32> {
+    read_totals(input, true)?;
+    part_one(input)
 }
 
 #[must_use]
 pub fn part_two(input: &str) -> Option<u32> {
-    let totals = read_totals_from_input(input);
-    Some(max_total_calories(&totals, 3).iter().sum())
+    Some(sum_of_top(input, 3))
+}
+
+#[must_use]
+pub fn part_two_strict(input: &str) -> Option<u32> {
+    read_totals(input, true)?;
+    part_two(input)
 }
 
 fn main() {
@@ -58,15 +179,158 @@ mod tests {
         assert_eq!(max_total_calories(&totals, 3), vec![24000, 11000, 10000]);
     }
 
+    #[test]
+    fn test_read_totals_from_input_crlf() {
+        let input = advent_of_code::read_file("examples", 1);
+        let crlf = input.replace('\n', "\r\n");
+        assert_eq!(
+            read_totals_from_input(&crlf),
+            read_totals_from_input(&input)
+        );
+        assert_eq!(
+            try_read_totals_from_input(&crlf),
+            try_read_totals_from_input(&input)
+        );
+    }
+
+    #[test]
+    fn test_try_read_totals_from_input() {
+        let input = advent_of_code::read_file("examples", 1);
+        assert_eq!(
+            try_read_totals_from_input(&input),
+            Ok(vec![6000, 4000, 11000, 24000, 10000])
+        );
+    }
+
+    #[test]
+    fn test_try_read_totals_from_input_non_numeric() {
+        assert_eq!(
+            try_read_totals_from_input("1000\nabc\n2000"),
+            Err(ParseCaloriesError {
+                line: 2,
+                text: "abc".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_read_totals_from_input_negative() {
+        assert_eq!(
+            try_read_totals_from_input("1000\n\n2000\n-300"),
+            Err(ParseCaloriesError {
+                line: 4,
+                text: "-300".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_totals_strict() {
+        assert_eq!(read_totals("1000\nabc\n2000", false), Some(vec![3000]));
+        assert_eq!(read_totals("1000\nabc\n2000", true), None);
+    }
+
+    #[test]
+    fn test_sum_of_top() {
+        let input = advent_of_code::read_file("examples", 1);
+        assert_eq!(sum_of_top(&input, 0), 0);
+        assert_eq!(sum_of_top(&input, 1), 24000);
+        assert_eq!(sum_of_top(&input, 5), 55000);
+        assert_eq!(sum_of_top(&input, 100), 55000);
+    }
+
+    #[test]
+    fn test_sum_of_top_empty_input() {
+        assert_eq!(sum_of_top("", 3), 0);
+    }
+
+    #[test]
+    fn test_totals_with_index() {
+        let input = advent_of_code::read_file("examples", 1);
+        assert_eq!(
+            totals_with_index(&input),
+            vec![(1, 6000), (2, 4000), (3, 11000), (4, 24000), (5, 10000)]
+        );
+    }
+
+    #[test]
+    fn test_max_total_calories_with_index() {
+        let input = advent_of_code::read_file("examples", 1);
+        let totals = totals_with_index(&input);
+        assert_eq!(max_total_calories_with_index(&totals, 1), vec![(4, 24000)]);
+        assert_eq!(
+            max_total_calories_with_index(&totals, 3),
+            vec![(4, 24000), (3, 11000), (5, 10000)]
+        );
+    }
+
+    #[test]
+    fn test_streaming_sum_of_top() {
+        let input = advent_of_code::read_file("examples", 1);
+        for quantity in [0, 1, 3, 5, 10] {
+            assert_eq!(
+                streaming_sum_of_top(&input, quantity),
+                sum_of_top(&input, quantity)
+            );
+        }
+    }
+
+    #[test]
+    fn test_streaming_sum_of_top_quantity_exceeds_elves() {
+        let input = advent_of_code::read_file("examples", 1);
+        for quantity in [6, 1_000, usize::MAX] {
+            assert_eq!(
+                streaming_sum_of_top(&input, quantity),
+                sum_of_top(&input, quantity)
+            );
+        }
+    }
+
+    #[test]
+    fn test_streaming_sum_of_top_large_input() {
+        let input: String = (0..100_000_u32)
+            .map(|elf| {
+                let first = (elf * 7919) % 10_007;
+                let second = (elf % 9_973) * 13;
+                format!("{first}\n{second}\n")
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        for quantity in [1, 3, 50] {
+            assert_eq!(
+                streaming_sum_of_top(&input, quantity),
+                max_total_calories(&read_totals_from_input(&input), quantity)
+                    .iter()
+                    .sum()
+            );
+        }
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 1);
         assert_eq!(part_one(&input), Some(24000));
     }
 
+    #[test]
+    fn test_part_one_strict() {
+        let input = advent_of_code::read_file("examples", 1);
+        assert_eq!(part_one_strict(&input), Some(24000));
+        assert_eq!(part_one("1000\nabc\n2000\n\n500"), Some(3000));
+        assert_eq!(part_one_strict("1000\nabc\n2000\n\n500"), None);
+    }
+
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 1);
         assert_eq!(part_two(&input), Some(45000));
     }
+
+    #[test]
+    fn test_part_two_strict() {
+        let input = advent_of_code::read_file("examples", 1);
+        assert_eq!(part_two_strict(&input), Some(45000));
+        assert_eq!(part_two("1000\n\n2000\n-300"), Some(3000));
+        assert_eq!(part_two_strict("1000\n\n2000\n-300"), None);
+    }
 }
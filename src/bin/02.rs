@@ -1,16 +1,229 @@
-fn part_one_score(game: &str) -> u32 {
-    match game {
-        "A X" => 4,
-        "A Y" => 8,
-        "A Z" => 3,
-        "B X" => 1,
-        "B Y" => 5,
-        "B Z" => 9,
-        "C X" => 7,
-        "C Y" => 2,
-        "C Z" => 6,
-        _ => 0,
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Shape {
+    fn beats(self) -> Self {
+        match self {
+            Shape::Rock => Shape::Scissors,
+            Shape::Paper => Shape::Rock,
+            Shape::Scissors => Shape::Paper,
+        }
+    }
+
+    fn loses_to(self) -> Self {
+        match self {
+            Shape::Rock => Shape::Paper,
+            Shape::Paper => Shape::Scissors,
+            Shape::Scissors => Shape::Rock,
+        }
+    }
+
+    fn outcome_against(self, opponent: Shape) -> Outcome {
+        if self == opponent {
+            Outcome::Draw
+        } else if self.beats() == opponent {
+            Outcome::Win
+        } else {
+            Outcome::Loss
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct ParseGameError;
+
+impl FromStr for Shape {
+    type Err = ParseGameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "A" => Ok(Shape::Rock),
+            "B" => Ok(Shape::Paper),
+            "C" => Ok(Shape::Scissors),
+            _ => Err(ParseGameError),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    fn shape_required(self, opponent: Shape) -> Shape {
+        match self {
+            Outcome::Loss => opponent.beats(),
+            Outcome::Draw => opponent,
+            Outcome::Win => opponent.loses_to(),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = ParseGameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "X" => Ok(Outcome::Loss),
+            "Y" => Ok(Outcome::Draw),
+            "Z" => Ok(Outcome::Win),
+            _ => Err(ParseGameError),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Round {
+    opponent: Shape,
+    code: char,
+}
+
+impl Round {
+    fn own_shape(&self) -> Result<Shape, ParseGameError> {
+        match self.code {
+            'X' => Ok(Shape::Rock),
+            'Y' => Ok(Shape::Paper),
+            'Z' => Ok(Shape::Scissors),
+            _ => Err(ParseGameError),
+        }
+    }
+
+    fn desired_outcome(&self) -> Result<Outcome, ParseGameError> {
+        self.code.to_string().parse()
+    }
+
+    fn resolve(&self, code_meaning: CodeMeaning) -> Option<(Shape, Outcome)> {
+        match code_meaning {
+            CodeMeaning::Shape => {
+                let own = self.own_shape().ok()?;
+                Some((own, own.outcome_against(self.opponent)))
+            }
+            CodeMeaning::Outcome => {
+                let outcome = self.desired_outcome().ok()?;
+                Some((outcome.shape_required(self.opponent), outcome))
+            }
+        }
+    }
+}
+
+impl FromStr for Round {
+    type Err = ParseGameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(' ').collect();
+        if parts.len() == 2 {
+            let opponent = parts[0].parse()?;
+            let mut chars = parts[1].chars();
+            match (chars.next(), chars.next()) {
+                (Some(code), None) => Ok(Round { opponent, code }),
+                _ => Err(ParseGameError),
+            }
+        } else {
+            Err(ParseGameError)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CodeMeaning {
+    Shape,
+    Outcome,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ScoringTable {
+    pub rock: u32,
+    pub paper: u32,
+    pub scissors: u32,
+    pub loss: u32,
+    pub draw: u32,
+    pub win: u32,
+}
+
+impl ScoringTable {
+    #[must_use]
+    pub fn standard() -> Self {
+        Self {
+            rock: 1,
+            paper: 2,
+            scissors: 3,
+            loss: 0,
+            draw: 3,
+            win: 6,
+        }
+    }
+
+    fn shape_score(&self, shape: Shape) -> u32 {
+        match shape {
+            Shape::Rock => self.rock,
+            Shape::Paper => self.paper,
+            Shape::Scissors => self.scissors,
+        }
+    }
+
+    fn outcome_score(&self, outcome: Outcome) -> u32 {
+        match outcome {
+            Outcome::Loss => self.loss,
+            Outcome::Draw => self.draw,
+            Outcome::Win => self.win,
+        }
     }
+
+    fn round_score(&self, round: &Round, code_meaning: CodeMeaning) -> Option<u32> {
+        let (own, outcome) = round.resolve(code_meaning)?;
+        Some(self.shape_score(own) + self.outcome_score(outcome))
+    }
+
+    fn game_score(&self, game: &str, code_meaning: CodeMeaning) -> u32 {
+        game.trim()
+            .parse::<Round>()
+            .ok()
+            .and_then(|round| self.round_score(&round, code_meaning))
+            .unwrap_or(0)
+    }
+}
+
+#[must_use]
+pub fn score_with(input: &str, table: &ScoringTable, code_meaning: CodeMeaning) -> u32 {
+    input
+        .lines()
+        .map(|game| table.game_score(game, code_meaning))
+        .sum()
+}
+
+#[must_use]
+pub fn outcome_breakdown(input: &str, part_two: bool) -> (u32, u32, u32) {
+    let code_meaning = if part_two {
+        CodeMeaning::Outcome
+    } else {
+        CodeMeaning::Shape
+    };
+
+    input
+        .lines()
+        .filter_map(|game| game.trim().parse::<Round>().ok())
+        .filter_map(|round| round.resolve(code_meaning))
+        .fold(
+            (0, 0, 0),
+            |(wins, draws, losses), (_, outcome)| match outcome {
+                Outcome::Win => (wins + 1, draws, losses),
+                Outcome::Draw => (wins, draws + 1, losses),
+                Outcome::Loss => (wins, draws, losses + 1),
+            },
+        )
+}
+
+fn part_one_score(game: &str) -> u32 {
+    ScoringTable::standard().game_score(game, CodeMeaning::Shape)
 }
 
 #[must_use]
@@ -20,18 +233,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 fn part_two_score(game: &str) -> u32 {
-    match game {
-        "A X" => 3,
-        "A Y" => 4,
-        "A Z" => 8,
-        "B X" => 1,
-        "B Y" => 5,
-        "B Z" => 9,
-        "C X" => 2,
-        "C Y" => 6,
-        "C Z" => 7,
-        _ => 0,
-    }
+    ScoringTable::standard().game_score(game, CodeMeaning::Outcome)
 }
 
 #[must_use]
@@ -50,6 +252,115 @@ fn main() {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_shape() {
+        assert_eq!("A".parse(), Ok(Shape::Rock));
+        assert_eq!("B".parse(), Ok(Shape::Paper));
+        assert_eq!("C".parse(), Ok(Shape::Scissors));
+        assert_eq!("X".parse::<Shape>(), Err(ParseGameError));
+        assert_eq!("D".parse::<Shape>(), Err(ParseGameError));
+    }
+
+    #[test]
+    fn test_parse_outcome() {
+        assert_eq!("X".parse(), Ok(Outcome::Loss));
+        assert_eq!("Y".parse(), Ok(Outcome::Draw));
+        assert_eq!("Z".parse(), Ok(Outcome::Win));
+        assert_eq!("A".parse::<Outcome>(), Err(ParseGameError));
+    }
+
+    #[test]
+    fn test_parse_round() {
+        assert_eq!(
+            "A Y".parse(),
+            Ok(Round {
+                opponent: Shape::Rock,
+                code: 'Y',
+            })
+        );
+        assert_eq!("D Y".parse::<Round>(), Err(ParseGameError));
+        assert_eq!("A YY".parse::<Round>(), Err(ParseGameError));
+        assert_eq!("A".parse::<Round>(), Err(ParseGameError));
+        assert_eq!("X A".parse::<Round>(), Err(ParseGameError));
+    }
+
+    #[test]
+    fn test_opponent_column_codes_rejected() {
+        assert_eq!(part_one_score("X A"), 0);
+        assert_eq!(part_two_score("X A"), 0);
+        assert_eq!(part_one_score("A A"), 0);
+        assert_eq!(part_two_score("A A"), 0);
+    }
+
+    #[test]
+    fn test_score_with_doubled_bonuses() {
+        let input = advent_of_code::read_file("examples", 2);
+        let doubled = ScoringTable {
+            loss: 0,
+            draw: 6,
+            win: 12,
+            ..ScoringTable::standard()
+        };
+        let standard = score_with(&input, &ScoringTable::standard(), CodeMeaning::Shape);
+        let shapes_only = score_with(
+            &input,
+            &ScoringTable {
+                loss: 0,
+                draw: 0,
+                win: 0,
+                ..ScoringTable::standard()
+            },
+            CodeMeaning::Shape,
+        );
+        let doubled_total = score_with(&input, &doubled, CodeMeaning::Shape);
+        assert_eq!(doubled_total, 24);
+        assert_eq!(doubled_total - shapes_only, 2 * (standard - shapes_only));
+    }
+
+    #[test]
+    fn test_score_with_whitespace_and_empty_lines() {
+        let table = ScoringTable::standard();
+        assert_eq!(
+            score_with("A Y  \n\nB X\t\n\nC Z\n", &table, CodeMeaning::Shape),
+            15
+        );
+    }
+
+    #[test]
+    fn test_outcome_breakdown() {
+        let input = advent_of_code::read_file("examples", 2);
+        assert_eq!(outcome_breakdown(&input, false), (1, 1, 1));
+        assert_eq!(outcome_breakdown(&input, true), (1, 1, 1));
+    }
+
+    #[test]
+    fn test_outcome_breakdown_relates_to_score() {
+        let input = advent_of_code::read_file("examples", 2);
+        for (part_two, total) in [(false, part_one(&input)), (true, part_two(&input))] {
+            let (wins, draws, losses) = outcome_breakdown(&input, part_two);
+            assert_eq!(wins + draws + losses, 3);
+
+            let table = ScoringTable {
+                loss: 0,
+                draw: 0,
+                win: 0,
+                ..ScoringTable::standard()
+            };
+            let code_meaning = if part_two {
+                CodeMeaning::Outcome
+            } else {
+                CodeMeaning::Shape
+            };
+            let shapes_only = score_with(&input, &table, code_meaning);
+            assert_eq!(total, Some(shapes_only + (wins * 6) + (draws * 3)));
+        }
+    }
+
+    #[test]
+    fn test_outcome_breakdown_skips_invalid_lines() {
+        assert_eq!(outcome_breakdown("A Y\nD W\n\nC Z", false), (1, 1, 0));
+    }
+
     #[test]
     fn test_part_one_score_a_y() {
         assert_eq!(part_one_score("A Y"), 8);
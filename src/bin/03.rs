@@ -1,45 +1,101 @@
-use std::collections::HashSet;
-
-fn priority(item: Option<&char>) -> u32 {
+fn priority(item: char) -> Option<u32> {
     match item {
-        None => 0,
-        Some(c) => {
-            if c.is_lowercase() {
-                (*c as u32) - 96
-            } else {
-                (*c as u32) - 38
-            }
-        }
+        'a'..='z' => Some(item as u32 - 96),
+        'A'..='Z' => Some(item as u32 - 38),
+        _ => None,
     }
 }
 
+fn item_mask(items: &str) -> u64 {
+    items
+        .chars()
+        .filter_map(priority)
+        .fold(0, |mask, priority| mask | (1 << (priority - 1)))
+}
+
+fn mask_priority(mask: u64) -> u32 {
+    if mask == 0 {
+        0
+    } else {
+        mask.trailing_zeros() + 1
+    }
+}
+
+fn mask_item(mask: u64) -> Option<char> {
+    let item = match mask_priority(mask) {
+        0 => return None,
+        priority @ 1..=26 => priority + 96,
+        priority => priority + 38,
+    };
+    char::from_u32(item)
+}
+
+fn compartments(backpack: &str) -> (&str, &str) {
+    let middle = backpack
+        .char_indices()
+        .nth(backpack.chars().count() / 2)
+        .map_or(backpack.len(), |(ix, _)| ix);
+    backpack.split_at(middle)
+}
+
+#[must_use]
+pub fn duplicate_item(backpack: &str) -> Option<char> {
+    let (compartment_one, compartment_two) = compartments(backpack);
+    mask_item(item_mask(compartment_one) & item_mask(compartment_two))
+}
+
 fn backpack_priority(backpack: &str) -> u32 {
-    let mut compartment_one: HashSet<char> = HashSet::new();
-    let mut compartment_two: HashSet<char> = HashSet::new();
+    duplicate_item(backpack).and_then(priority).unwrap_or(0)
+}
 
-    let middle = backpack.len() / 2;
+#[derive(Debug, PartialEq)]
+pub enum RucksackError {
+    OddLength(usize),
+    NoCommonItem,
+}
 
-    for (pos, char) in backpack.chars().enumerate() {
-        if pos < middle {
-            compartment_one.insert(char);
-        } else {
-            compartment_two.insert(char);
-        }
+/// # Errors
+///
+/// Will return `Err` if the backpack cannot be split evenly into two compartments, or if the
+/// compartments have no item in common.
+pub fn try_backpack_priority(backpack: &str) -> Result<u32, RucksackError> {
+    let length = backpack.chars().count();
+    if !length.is_multiple_of(2) {
+        return Err(RucksackError::OddLength(length));
     }
 
-    let mut both_compartments = compartment_one.intersection(&compartment_two);
-    priority(both_compartments.next())
+    let (compartment_one, compartment_two) = compartments(backpack);
+
+    match mask_priority(item_mask(compartment_one) & item_mask(compartment_two)) {
+        0 => Err(RucksackError::NoCommonItem),
+        priority => Ok(priority),
+    }
+}
+
+fn group_badge_n(lines: &[&str]) -> Option<char> {
+    lines
+        .iter()
+        .map(|line| item_mask(line))
+        .reduce(|common, other| common & other)
+        .and_then(mask_item)
+}
+
+#[must_use]
+pub fn group_badge(first: &str, second: &str, third: &str) -> Option<char> {
+    group_badge_n(&[first, second, third])
+}
+
+fn group_badge_priority_n(lines: &[&str]) -> u32 {
+    group_badge_n(lines).and_then(priority).unwrap_or(0)
 }
 
-fn group_badge_priority(first: &str, second: &str, third: &str) -> u32 {
-    let first_set = first.chars().collect::<HashSet<char>>();
-    let second_set = second.chars().collect::<HashSet<char>>();
-    let third_set = third.chars().collect::<HashSet<char>>();
-    priority(
-        first_set
-            .intersection(&second_set)
-            .find(|item| third_set.contains(item)),
-    )
+#[must_use]
+pub fn sum_group_badges(input: &str, group_size: usize) -> Option<u32> {
+    let lines: Vec<&str> = input.lines().collect();
+    if group_size == 0 || !lines.len().is_multiple_of(group_size) {
+        return None;
+    }
+    Some(lines.chunks(group_size).map(group_badge_priority_n).sum())
 }
 
 #[must_use]
@@ -50,17 +106,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 #[must_use]
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut total = 0;
-    let mut backpacks = input.lines().peekable();
-
-    while backpacks.peek().is_some() {
-        let first = backpacks.next().unwrap_or("");
-        let second = backpacks.next().unwrap_or("");
-        let third = backpacks.next().unwrap_or("");
-        total += group_badge_priority(first, second, third);
-    }
-
-    Some(total)
+    sum_group_badges(input, 3)
 }
 
 fn main() {
@@ -72,6 +118,52 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
+
+    fn hashset_backpack_priority(backpack: &str) -> u32 {
+        let middle = backpack.len() / 2;
+        let compartment_one: HashSet<char> = backpack.chars().take(middle).collect();
+        let compartment_two: HashSet<char> = backpack.chars().skip(middle).collect();
+        let common = compartment_one.intersection(&compartment_two).next();
+        common.and_then(|item| priority(*item)).unwrap_or(0)
+    }
+
+    fn hashset_group_badge_priority(lines: &[&str]) -> u32 {
+        let mut sets = lines
+            .iter()
+            .map(|line| line.chars().collect::<HashSet<char>>());
+        let common = sets.next().map(|first| {
+            sets.fold(first, |common, other| {
+                common.intersection(&other).copied().collect()
+            })
+        });
+        common
+            .and_then(|common| common.into_iter().find_map(priority))
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_item_mask() {
+        assert_eq!(item_mask(""), 0);
+        assert_eq!(item_mask("a"), 1);
+        assert_eq!(item_mask("aZ"), 1 | 1 << 51);
+        assert_eq!(item_mask("bbB"), 1 << 1 | 1 << 27);
+    }
+
+    #[test]
+    fn test_masks_match_hashsets() {
+        let input = advent_of_code::read_file("examples", 3);
+        let lines: Vec<&str> = input.lines().collect();
+        for line in &lines {
+            assert_eq!(backpack_priority(line), hashset_backpack_priority(line));
+        }
+        for group in lines.chunks(3) {
+            assert_eq!(
+                group_badge_priority_n(group),
+                hashset_group_badge_priority(group)
+            );
+        }
+    }
 
     #[test]
     fn test_first_backpack() {
@@ -88,6 +180,43 @@ mod tests {
         assert_eq!(backpack_priority("PmmdzqPrVvPwwTWBwg"), 42);
     }
 
+    #[test]
+    fn test_duplicate_item() {
+        assert_eq!(duplicate_item("vJrwpWtwJgWrhcsFMMfFFhFp"), Some('p'));
+        assert_eq!(
+            duplicate_item("jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL"),
+            Some('L')
+        );
+        assert_eq!(duplicate_item("abcdef"), None);
+    }
+
+    #[test]
+    fn test_duplicate_item_non_ascii() {
+        assert_eq!(duplicate_item("éa"), None);
+        assert_eq!(duplicate_item("aéaé"), Some('a'));
+    }
+
+    #[test]
+    fn test_try_backpack_priority() {
+        assert_eq!(try_backpack_priority("vJrwpWtwJgWrhcsFMMfFFhFp"), Ok(16));
+    }
+
+    #[test]
+    fn test_try_backpack_priority_odd_length() {
+        assert_eq!(
+            try_backpack_priority("vJrwpWtwJgWrhcsFMMfFFhF"),
+            Err(RucksackError::OddLength(23))
+        );
+    }
+
+    #[test]
+    fn test_try_backpack_priority_no_common_item() {
+        assert_eq!(
+            try_backpack_priority("abcDEF"),
+            Err(RucksackError::NoCommonItem)
+        );
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 3);
@@ -97,15 +226,50 @@ mod tests {
     #[test]
     fn test_first_group() {
         assert_eq!(
-            group_badge_priority(
+            group_badge_priority_n(&[
                 "vJrwpWtwJgWrhcsFMMfFFhFp",
                 "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
                 "PmmdzqPrVvPwwTWBwg"
-            ),
+            ]),
             18
         );
     }
 
+    #[test]
+    fn test_group_badge() {
+        assert_eq!(
+            group_badge(
+                "vJrwpWtwJgWrhcsFMMfFFhFp",
+                "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+                "PmmdzqPrVvPwwTWBwg"
+            ),
+            Some('r')
+        );
+        assert_eq!(group_badge("abc", "def", "ghi"), None);
+    }
+
+    #[test]
+    fn test_group_badge_priority_n() {
+        assert_eq!(group_badge_priority_n(&["abc", "cde"]), 3);
+        assert_eq!(group_badge_priority_n(&["abZ", "Zcd", "eZf", "gZ"]), 52);
+        assert_eq!(group_badge_priority_n(&["abc", "def"]), 0);
+    }
+
+    #[test]
+    fn test_sum_group_badges() {
+        let input = advent_of_code::read_file("examples", 3);
+        assert_eq!(sum_group_badges(&input, 3), Some(70));
+        assert_eq!(sum_group_badges("abc\ncde\nxyZ\nZzz", 2), Some(3 + 52));
+    }
+
+    #[test]
+    fn test_sum_group_badges_ragged() {
+        let input = advent_of_code::read_file("examples", 3);
+        assert_eq!(sum_group_badges(&input, 4), None);
+        assert_eq!(sum_group_badges(&input, 0), None);
+        assert_eq!(sum_group_badges("abc\ncde\nxyZ", 2), None);
+    }
+
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 3);
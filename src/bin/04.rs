@@ -1,13 +1,13 @@
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq)]
-struct Range {
+pub struct Range {
     start: u32,
     finish: u32,
 }
 
 #[derive(Debug, PartialEq)]
-struct ParseRangeError;
+pub struct ParseRangeError;
 
 impl FromStr for Range {
     type Err = ParseRangeError;
@@ -30,80 +30,89 @@ impl Range {
     }
 
     fn has_overlap_with_other(&self, other: &Range) -> bool {
-        (self.start >= other.start && self.start <= other.finish)
-            || (self.finish >= other.start && self.finish <= other.finish)
+        self.start <= other.finish && other.start <= self.finish
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Range) -> Option<Range> {
+        let start = self.start.max(other.start);
+        let finish = self.finish.min(other.finish);
+        if start <= finish {
+            Some(Range { start, finish })
+        } else {
+            None
+        }
     }
 }
 
 #[derive(Debug, PartialEq)]
-struct Pair {
-    first: Range,
-    second: Range,
+struct RangeGroup {
+    ranges: Vec<Range>,
 }
 
-impl FromStr for Pair {
+impl FromStr for RangeGroup {
     type Err = ParseRangeError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let ranges: Vec<&str> = s.split(',').collect();
-        if ranges.len() == 2 {
-            let first: Range = ranges[0].parse()?;
-            let second: Range = ranges[1].parse()?;
-            Ok(Pair { first, second })
-        } else {
-            Err(ParseRangeError)
+        let mut ranges = Vec::new();
+        for range in s.split(',') {
+            ranges.push(range.parse()?);
+        }
+        if ranges.len() < 2 {
+            return Err(ParseRangeError);
         }
+        Ok(RangeGroup { ranges })
     }
 }
 
-impl Pair {
-    fn is_overlapping(&self) -> bool {
-        self.first.has_overlap_with_other(&self.second)
-            || self.second.has_overlap_with_other(&self.first)
+impl RangeGroup {
+    fn any_pair_overlaps(&self) -> bool {
+        self.ranges.iter().enumerate().any(|(ix, first)| {
+            self.ranges[ix + 1..]
+                .iter()
+                .any(|second| first.has_overlap_with_other(second))
+        })
     }
 
-    fn is_fully_overlapping(&self) -> bool {
-        self.first.is_fully_contained_by_other(&self.second)
-            || self.second.is_fully_contained_by_other(&self.first)
+    fn all_covered_by_one(&self) -> bool {
+        self.ranges.iter().any(|outer| {
+            self.ranges
+                .iter()
+                .all(|inner| inner.is_fully_contained_by_other(outer))
+        })
     }
 }
 
-fn read_pairs(input: &str) -> Result<Vec<Pair>, ParseRangeError> {
-    let mut pairs: Vec<Pair> = Vec::new();
+fn read_groups(input: &str) -> Result<Vec<RangeGroup>, ParseRangeError> {
+    let mut groups: Vec<RangeGroup> = Vec::new();
     for line in input.lines() {
-        let pair: Result<Pair, ParseRangeError> = line.parse();
-        match pair {
+        let group: Result<RangeGroup, ParseRangeError> = line.parse();
+        match group {
             Err(e) => return Err(e),
-            Ok(p) => pairs.push(p),
+            Ok(g) => groups.push(g),
         };
     }
-    Ok(pairs)
+    Ok(groups)
+}
+
+fn count_overlaps(input: &str) -> Result<(u32, u32), ParseRangeError> {
+    let groups = read_groups(input)?;
+    Ok(groups.iter().fold((0, 0), |(full, any), group| {
+        (
+            full + u32::from(group.all_covered_by_one()),
+            any + u32::from(group.any_pair_overlaps()),
+        )
+    }))
 }
 
 #[must_use]
 pub fn part_one(input: &str) -> Option<u32> {
-    match read_pairs(input) {
-        Err(_) => None,
-        Ok(pairs) => Some(
-            pairs
-                .iter()
-                .map(|pair| u32::from(pair.is_fully_overlapping()))
-                .sum(),
-        ),
-    }
+    count_overlaps(input).ok().map(|(full, _)| full)
 }
 
 #[must_use]
 pub fn part_two(input: &str) -> Option<u32> {
-    match read_pairs(input) {
-        Err(_) => None,
-        Ok(pairs) => Some(
-            pairs
-                .iter()
-                .map(|pair| u32::from(pair.is_overlapping()))
-                .sum(),
-        ),
-    }
+    count_overlaps(input).ok().map(|(_, any)| any)
 }
 
 fn main() {
@@ -117,104 +126,129 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_read_pairs() {
+    fn test_read_groups() {
         let input = advent_of_code::read_file("examples", 4);
 
         assert_eq!(
-            read_pairs(&input),
+            read_groups(&input),
             Ok(vec![
-                Pair {
-                    first: Range {
-                        start: 2,
-                        finish: 4
-                    },
-                    second: Range {
-                        start: 6,
-                        finish: 8
-                    },
+                RangeGroup {
+                    ranges: vec![
+                        Range {
+                            start: 2,
+                            finish: 4
+                        },
+                        Range {
+                            start: 6,
+                            finish: 8
+                        }
+                    ]
                 },
-                Pair {
-                    first: Range {
-                        start: 2,
-                        finish: 3
-                    },
-                    second: Range {
-                        start: 4,
-                        finish: 5
-                    },
+                RangeGroup {
+                    ranges: vec![
+                        Range {
+                            start: 2,
+                            finish: 3
+                        },
+                        Range {
+                            start: 4,
+                            finish: 5
+                        }
+                    ]
                 },
-                Pair {
-                    first: Range {
-                        start: 5,
-                        finish: 7
-                    },
-                    second: Range {
-                        start: 7,
-                        finish: 9
-                    },
+                RangeGroup {
+                    ranges: vec![
+                        Range {
+                            start: 5,
+                            finish: 7
+                        },
+                        Range {
+                            start: 7,
+                            finish: 9
+                        }
+                    ]
                 },
-                Pair {
-                    first: Range {
-                        start: 2,
-                        finish: 8
-                    },
-                    second: Range {
-                        start: 3,
-                        finish: 7
-                    },
+                RangeGroup {
+                    ranges: vec![
+                        Range {
+                            start: 2,
+                            finish: 8
+                        },
+                        Range {
+                            start: 3,
+                            finish: 7
+                        }
+                    ]
                 },
-                Pair {
-                    first: Range {
-                        start: 6,
-                        finish: 6
-                    },
-                    second: Range {
-                        start: 4,
-                        finish: 6
-                    },
+                RangeGroup {
+                    ranges: vec![
+                        Range {
+                            start: 6,
+                            finish: 6
+                        },
+                        Range {
+                            start: 4,
+                            finish: 6
+                        }
+                    ]
                 },
-                Pair {
-                    first: Range {
-                        start: 2,
-                        finish: 6
-                    },
-                    second: Range {
-                        start: 4,
-                        finish: 8
-                    },
+                RangeGroup {
+                    ranges: vec![
+                        Range {
+                            start: 2,
+                            finish: 6
+                        },
+                        Range {
+                            start: 4,
+                            finish: 8
+                        }
+                    ]
                 },
             ])
         );
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_pair_four_fully_overlaps() {
-        let pair = Pair {
-            first: Range {
-                start: 2,
-                finish: 8,
-            },
-            second: Range {
-                start: 3,
-                finish: 7,
-            },
+        let pair = RangeGroup {
+            ranges: vec![
+                Range {
+                    start: 2,
+                    finish: 8,
+                },
+                Range {
+                    start: 3,
+                    finish: 7,
+                },
+            ],
         };
-        assert_eq!(pair.is_fully_overlapping(), true);
+        assert_eq!(pair.all_covered_by_one(), true);
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_pair_one_doesnt_fully_overlap() {
-        let pair = Pair {
-            first: Range {
-                start: 2,
-                finish: 4,
-            },
-            second: Range {
-                start: 6,
-                finish: 8,
-            },
+        let pair = RangeGroup {
+            ranges: vec![
+                Range {
+                    start: 2,
+                    finish: 4,
+                },
+                Range {
+                    start: 6,
+                    finish: 8,
+                },
+            ],
         };
-        assert_eq!(pair.is_fully_overlapping(), false);
+        assert_eq!(pair.all_covered_by_one(), false);
+    }
+
+    #[test]
+    fn test_count_overlaps() {
+        let input = advent_of_code::read_file("examples", 4);
+        assert_eq!(count_overlaps(&input), Ok((2, 4)));
+        assert_eq!(count_overlaps("2-4,6"), Err(ParseRangeError));
     }
 
     #[test]
@@ -224,33 +258,181 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_pair_one_doesnt_overlap() {
-        let pair = Pair {
-            first: Range {
-                start: 2,
-                finish: 4,
-            },
-            second: Range {
-                start: 6,
-                finish: 8,
-            },
+        let pair = RangeGroup {
+            ranges: vec![
+                Range {
+                    start: 2,
+                    finish: 4,
+                },
+                Range {
+                    start: 6,
+                    finish: 8,
+                },
+            ],
         };
-        assert_eq!(pair.is_overlapping(), false);
+        assert_eq!(pair.any_pair_overlaps(), false);
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_pair_three_overlaps() {
-        let pair = Pair {
-            first: Range {
-                start: 5,
-                finish: 7,
-            },
-            second: Range {
-                start: 7,
-                finish: 9,
-            },
+        let pair = RangeGroup {
+            ranges: vec![
+                Range {
+                    start: 5,
+                    finish: 7,
+                },
+                Range {
+                    start: 7,
+                    finish: 9,
+                },
+            ],
+        };
+        assert_eq!(pair.any_pair_overlaps(), true);
+    }
+
+    #[test]
+    fn test_overlap_with_contained_range() {
+        let outer = Range {
+            start: 1,
+            finish: 10,
+        };
+        let inner = Range {
+            start: 4,
+            finish: 5,
+        };
+        assert!(outer.has_overlap_with_other(&inner));
+        assert!(inner.has_overlap_with_other(&outer));
+
+        let mut pair = RangeGroup {
+            ranges: vec![outer, inner],
+        };
+        assert!(pair.any_pair_overlaps());
+
+        pair.ranges.reverse();
+        assert!(pair.any_pair_overlaps());
+    }
+
+    #[test]
+    fn test_three_range_group() {
+        let disjoint: RangeGroup = "1-2,4-5,7-8"
+            .parse()
+            .unwrap_or(RangeGroup { ranges: vec![] });
+        assert_eq!(disjoint.ranges.len(), 3);
+        assert!(!disjoint.any_pair_overlaps());
+        assert!(!disjoint.all_covered_by_one());
+
+        let chained: RangeGroup = "1-2,7-8,2-3"
+            .parse()
+            .unwrap_or(RangeGroup { ranges: vec![] });
+        assert!(chained.any_pair_overlaps());
+        assert!(!chained.all_covered_by_one());
+
+        let covered: RangeGroup = "3-4,1-9,5-7"
+            .parse()
+            .unwrap_or(RangeGroup { ranges: vec![] });
+        assert!(covered.any_pair_overlaps());
+        assert!(covered.all_covered_by_one());
+    }
+
+    #[test]
+    fn test_four_range_group() {
+        let disjoint: RangeGroup = "1-1,3-3,5-5,7-7"
+            .parse()
+            .unwrap_or(RangeGroup { ranges: vec![] });
+        assert_eq!(disjoint.ranges.len(), 4);
+        assert!(!disjoint.any_pair_overlaps());
+
+        let last_pair: RangeGroup = "1-1,3-3,5-6,6-7"
+            .parse()
+            .unwrap_or(RangeGroup { ranges: vec![] });
+        assert!(last_pair.any_pair_overlaps());
+        assert!(!last_pair.all_covered_by_one());
+
+        let covered: RangeGroup = "2-3,4-5,1-8,6-8"
+            .parse()
+            .unwrap_or(RangeGroup { ranges: vec![] });
+        assert!(covered.all_covered_by_one());
+
+        assert_eq!(
+            "1-2,3-4,x-5,6-7".parse::<RangeGroup>(),
+            Err(ParseRangeError)
+        );
+    }
+
+    #[test]
+    fn test_single_range_group() {
+        assert_eq!("1-3".parse::<RangeGroup>(), Err(ParseRangeError));
+    }
+
+    #[test]
+    fn test_intersection_disjoint() {
+        let first = Range {
+            start: 2,
+            finish: 4,
+        };
+        let second = Range {
+            start: 6,
+            finish: 8,
+        };
+        assert_eq!(first.intersection(&second), None);
+        assert_eq!(second.intersection(&first), None);
+    }
+
+    #[test]
+    fn test_intersection_touching() {
+        let first = Range {
+            start: 5,
+            finish: 7,
+        };
+        let second = Range {
+            start: 7,
+            finish: 9,
+        };
+        let expected = Some(Range {
+            start: 7,
+            finish: 7,
+        });
+        assert_eq!(first.intersection(&second), expected);
+        assert_eq!(second.intersection(&first), expected);
+    }
+
+    #[test]
+    fn test_intersection_partial() {
+        let first = Range {
+            start: 2,
+            finish: 6,
+        };
+        let second = Range {
+            start: 4,
+            finish: 8,
+        };
+        let expected = Some(Range {
+            start: 4,
+            finish: 6,
+        });
+        assert_eq!(first.intersection(&second), expected);
+        assert_eq!(second.intersection(&first), expected);
+    }
+
+    #[test]
+    fn test_intersection_contained() {
+        let first = Range {
+            start: 2,
+            finish: 8,
+        };
+        let second = Range {
+            start: 3,
+            finish: 7,
         };
-        assert_eq!(pair.is_overlapping(), true);
+        let expected = Some(Range {
+            start: 3,
+            finish: 7,
+        });
+        assert_eq!(first.intersection(&second), expected);
+        assert_eq!(second.intersection(&first), expected);
     }
 
     #[test]
@@ -27,7 +27,12 @@ impl FromStr for Action {
     }
 }
 
-fn parse_input(input: &str) -> (Vec<String>, Vec<Action>) {
+fn is_stack_footer(line: &str) -> bool {
+    line.chars().any(|ch| ch.is_ascii_digit())
+        && line.chars().all(|ch| ch.is_ascii_digit() || ch == ' ')
+}
+
+fn parse_input(input: &str) -> (Vec<Vec<char>>, Vec<Action>) {
     let mut stacks = Vec::new();
     let mut actions = Vec::new();
 
@@ -36,12 +41,29 @@ fn parse_input(input: &str) -> (Vec<String>, Vec<Action>) {
         return (stacks, actions);
     }
 
-    for line in parts[0].split('\n') {
-        for (ix, pos) in (1..line.len()).step_by(4).enumerate() {
-            let ch = line.chars().nth(pos).unwrap_or(' ');
-            if stacks.len() <= ix {
-                stacks.push(String::new());
-            }
+    let mut drawing: Vec<&str> = parts[0].lines().collect();
+    let stack_count = match drawing.last() {
+        Some(footer) if is_stack_footer(footer) => {
+            let count = footer.split_whitespace().count();
+            drawing.pop();
+            count
+        }
+        _ => drawing
+            .iter()
+            .map(|line| line.chars().count().div_ceil(4))
+            .max()
+            .unwrap_or(0),
+    };
+
+    stacks.resize(stack_count, Vec::new());
+    for line in drawing.iter().rev() {
+        for (ix, ch) in line
+            .chars()
+            .skip(1)
+            .step_by(4)
+            .take(stack_count)
+            .enumerate()
+        {
             if ch.is_alphabetic() {
                 stacks[ix].push(ch);
             }
@@ -57,31 +79,72 @@ fn parse_input(input: &str) -> (Vec<String>, Vec<Action>) {
     (stacks, actions)
 }
 
-fn do_action(stacks: &[String], action: &Action, flip_moved: bool) -> Vec<String> {
-    let moved: String = {
-        let picked_up = stacks[action.from - 1][..action.quantity].chars();
-        if flip_moved {
-            picked_up.rev().collect()
-        } else {
-            picked_up.collect()
+trait CraneMode {
+    fn rearrange(picked: &[char]) -> Vec<char>;
+}
+
+struct CrateMover9000;
+
+impl CraneMode for CrateMover9000 {
+    fn rearrange(picked: &[char]) -> Vec<char> {
+        picked.iter().rev().copied().collect()
+    }
+}
+
+struct CrateMover9001;
+
+impl CraneMode for CrateMover9001 {
+    fn rearrange(picked: &[char]) -> Vec<char> {
+        picked.to_vec()
+    }
+}
+
+fn do_action<C: CraneMode>(stacks: &mut [Vec<char>], action: &Action) {
+    let mut moved = match stacks.get_mut(action.from.wrapping_sub(1)) {
+        Some(stack) => {
+            let picked = stack.split_off(stack.len().saturating_sub(action.quantity));
+            C::rearrange(&picked)
         }
+        None => return,
     };
+    if let Some(stack) = stacks.get_mut(action.to.wrapping_sub(1)) {
+        stack.append(&mut moved);
+    }
+}
 
-    let mut result = Vec::new();
-    for (ix, stack) in stacks.iter().enumerate() {
-        let stack = {
-            if ix + 1 == action.from {
-                stack[action.quantity..].to_string()
-            } else if ix + 1 == action.to {
-                moved.clone() + stack
-            } else {
-                stack.clone()
-            }
-        };
-        result.push(stack);
+#[derive(Debug, PartialEq)]
+enum MoveError {
+    NoSuchStack(usize),
+    NotEnoughCrates { stack: usize, available: usize },
+}
+
+fn try_do_action<C: CraneMode>(
+    mut stacks: Vec<Vec<char>>,
+    action: &Action,
+) -> Result<Vec<Vec<char>>, MoveError> {
+    let available = match stacks.get(action.from.wrapping_sub(1)) {
+        Some(stack) => stack.len(),
+        None => return Err(MoveError::NoSuchStack(action.from)),
+    };
+    if action.to == 0 || action.to > stacks.len() {
+        return Err(MoveError::NoSuchStack(action.to));
+    }
+    if action.quantity > available {
+        return Err(MoveError::NotEnoughCrates {
+            stack: action.from,
+            available,
+        });
     }
 
-    result
+    do_action::<C>(&mut stacks, action);
+    Ok(stacks)
+}
+
+fn top_crates(stacks: &[Vec<char>]) -> String {
+    stacks
+        .iter()
+        .map(|stack| stack.last().copied().unwrap_or(' '))
+        .collect()
 }
 
 #[must_use]
@@ -89,15 +152,10 @@ pub fn part_one(input: &str) -> Option<String> {
     let (mut stacks, actions) = parse_input(input);
 
     for action in actions {
-        stacks = do_action(&stacks, &action, true);
+        stacks = try_do_action::<CrateMover9000>(stacks, &action).ok()?;
     }
 
-    Some(
-        stacks
-            .iter()
-            .map(|stack| stack.chars().next().unwrap_or(' '))
-            .collect(),
-    )
+    Some(top_crates(&stacks))
 }
 
 #[must_use]
@@ -105,15 +163,10 @@ pub fn part_two(input: &str) -> Option<String> {
     let (mut stacks, actions) = parse_input(input);
 
     for action in actions {
-        stacks = do_action(&stacks, &action, false);
+        stacks = try_do_action::<CrateMover9001>(stacks, &action).ok()?;
     }
 
-    Some(
-        stacks
-            .iter()
-            .map(|stack| stack.chars().next().unwrap_or(' '))
-            .collect(),
-    )
+    Some(top_crates(&stacks))
 }
 
 fn main() {
@@ -131,7 +184,7 @@ mod tests {
         let input = advent_of_code::read_file("examples", 5);
         let (stacks, moves) = parse_input(&input);
 
-        assert_eq!(stacks, vec!["NZ", "DCM", "P"]);
+        assert_eq!(stacks, vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]);
         assert_eq!(
             moves,
             vec![
@@ -159,26 +212,195 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_input_trimmed_rows() {
+        let input = concat![
+            "    [D]\n",
+            "[N] [C]\n",
+            "[Z] [M] [P]\n",
+            " 1   2   3   4\n",
+            "\n",
+            "move 1 from 2 to 1\n",
+        ];
+        let (stacks, moves) = parse_input(input);
+
+        assert_eq!(
+            stacks,
+            vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P'], vec![]]
+        );
+        assert_eq!(moves.len(), 1);
+    }
+
+    #[test]
+    fn test_is_stack_footer() {
+        assert!(is_stack_footer(" 1   2   3 "));
+        assert!(is_stack_footer(" 1   2   3"));
+        assert!(!is_stack_footer("[Z] [M] [P]"));
+        assert!(!is_stack_footer("   "));
+    }
+
     #[test]
     fn test_first_action() {
-        let before = vec!["NZ".to_string(), "DCM".to_string(), "P".to_string()];
+        let mut stacks = vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']];
         let action = Action {
             quantity: 1,
             from: 2,
             to: 1,
         };
-        assert_eq!(do_action(&before, &action, true), vec!["DNZ", "CM", "P"],);
+        do_action::<CrateMover9000>(&mut stacks, &action);
+        assert_eq!(stacks, vec![vec!['Z', 'N', 'D'], vec!['M', 'C'], vec!['P']]);
     }
 
     #[test]
     fn test_second_action() {
-        let before = vec!["DNZ".to_string(), "CM".to_string(), "P".to_string()];
+        let mut stacks = vec![vec!['Z', 'N', 'D'], vec!['M', 'C'], vec!['P']];
         let action = Action {
             quantity: 3,
             from: 1,
             to: 3,
         };
-        assert_eq!(do_action(&before, &action, true), vec!["", "CM", "ZNDP"],);
+        do_action::<CrateMover9000>(&mut stacks, &action);
+        assert_eq!(
+            stacks,
+            vec![vec![], vec!['M', 'C'], vec!['P', 'D', 'N', 'Z']]
+        );
+    }
+
+    #[test]
+    fn test_action_from_empty_stack() {
+        let mut stacks = vec![vec![], vec!['M', 'C'], vec!['P']];
+        let action = Action {
+            quantity: 2,
+            from: 1,
+            to: 3,
+        };
+        do_action::<CrateMover9000>(&mut stacks, &action);
+        assert_eq!(stacks, vec![vec![], vec!['M', 'C'], vec!['P']]);
+        assert_eq!(top_crates(&stacks), " CP");
+    }
+
+    #[test]
+    fn test_action_moving_zero_crates() {
+        let action = Action {
+            quantity: 0,
+            from: 1,
+            to: 2,
+        };
+        assert_eq!("move 0 from 1 to 2".parse::<Action>().as_ref(), Ok(&action));
+        let before = vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']];
+
+        let mut stacks = before.clone();
+        do_action::<CrateMover9000>(&mut stacks, &action);
+        assert_eq!(stacks, before);
+        do_action::<CrateMover9001>(&mut stacks, &action);
+        assert_eq!(stacks, before);
+
+        assert_eq!(
+            try_do_action::<CrateMover9000>(before.clone(), &action),
+            Ok(before.clone())
+        );
+        assert_eq!(
+            try_do_action::<CrateMover9001>(before.clone(), &action),
+            Ok(before)
+        );
+    }
+
+    struct SortingCrane;
+
+    impl CraneMode for SortingCrane {
+        fn rearrange(picked: &[char]) -> Vec<char> {
+            let mut sorted = picked.to_vec();
+            sorted.sort_unstable();
+            sorted
+        }
+    }
+
+    #[test]
+    fn test_custom_crane_mode() {
+        let mut stacks = vec![vec!['A', 'Q', 'B', 'X'], vec!['P']];
+        let action = Action {
+            quantity: 3,
+            from: 1,
+            to: 2,
+        };
+        do_action::<SortingCrane>(&mut stacks, &action);
+        assert_eq!(stacks, vec![vec!['A'], vec!['P', 'B', 'Q', 'X']]);
+    }
+
+    #[test]
+    fn test_try_do_action() {
+        let stacks = vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']];
+        let action = Action {
+            quantity: 1,
+            from: 2,
+            to: 1,
+        };
+        assert_eq!(
+            try_do_action::<CrateMover9000>(stacks, &action),
+            Ok(vec![vec!['Z', 'N', 'D'], vec!['M', 'C'], vec!['P']])
+        );
+    }
+
+    #[test]
+    fn test_try_do_action_invalid_from() {
+        let stacks = vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']];
+        let action = Action {
+            quantity: 1,
+            from: 4,
+            to: 1,
+        };
+        assert_eq!(
+            try_do_action::<CrateMover9000>(stacks.clone(), &action),
+            Err(MoveError::NoSuchStack(4))
+        );
+        let action = Action {
+            quantity: 1,
+            from: 0,
+            to: 1,
+        };
+        assert_eq!(
+            try_do_action::<CrateMover9000>(stacks, &action),
+            Err(MoveError::NoSuchStack(0))
+        );
+    }
+
+    #[test]
+    fn test_try_do_action_invalid_to() {
+        let stacks = vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']];
+        let action = Action {
+            quantity: 1,
+            from: 1,
+            to: 4,
+        };
+        assert_eq!(
+            try_do_action::<CrateMover9000>(stacks, &action),
+            Err(MoveError::NoSuchStack(4))
+        );
+    }
+
+    #[test]
+    fn test_try_do_action_too_many_crates() {
+        let stacks = vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']];
+        let action = Action {
+            quantity: 3,
+            from: 1,
+            to: 2,
+        };
+        assert_eq!(
+            try_do_action::<CrateMover9001>(stacks, &action),
+            Err(MoveError::NotEnoughCrates {
+                stack: 1,
+                available: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_move_gives_none() {
+        let input = advent_of_code::read_file("examples", 5)
+            .replace("move 1 from 1 to 2", "move 9 from 1 to 2");
+        assert_eq!(part_one(&input), None);
+        assert_eq!(part_two(&input), None);
     }
 
     #[test]
@@ -1,21 +1,56 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
-fn marker_location(input: &str, distinct_chars: usize) -> Option<u32> {
+fn marker_windows(input: &str, distinct_chars: usize) -> impl Iterator<Item = (usize, &str)> {
     let mut last_seen: HashMap<char, usize> = HashMap::new();
+    let mut offsets: VecDeque<usize> = VecDeque::new();
 
-    for (pos, ch) in input.chars().enumerate() {
-        last_seen.insert(ch, pos);
+    input
+        .char_indices()
+        .enumerate()
+        .filter_map(move |(pos, (offset, ch))| {
+            last_seen.insert(ch, pos);
+            offsets.push_back(offset);
 
-        if pos >= distinct_chars {
-            let purge_earlier_than = 1 + pos - distinct_chars;
-            last_seen.retain(|_, old_pos| *old_pos >= purge_earlier_than);
-        }
-        if last_seen.len() == distinct_chars {
-            return Some(u32::try_from(pos + 1).unwrap_or(0));
-        }
-    }
+            if pos >= distinct_chars {
+                let purge_earlier_than = 1 + pos - distinct_chars;
+                last_seen.retain(|_, old_pos| *old_pos >= purge_earlier_than);
+                offsets.pop_front();
+            }
+            if last_seen.len() == distinct_chars {
+                let start = offsets.front().copied().unwrap_or(offset);
+                Some((pos + 1, &input[start..offset + ch.len_utf8()]))
+            } else {
+                None
+            }
+        })
+}
 
-    None
+fn marker_window(input: &str, distinct_chars: usize) -> Option<(usize, &str)> {
+    marker_windows(input, distinct_chars).next()
+}
+
+#[must_use]
+pub fn all_markers(input: &str, distinct_chars: usize) -> Vec<u32> {
+    marker_windows(input, distinct_chars)
+        .map(|(end, _)| u32::try_from(end - distinct_chars).unwrap_or(0))
+        .collect()
+}
+
+// Positions are counted in chars; use this for a byte offset that is safe to slice the input at.
+#[must_use]
+pub fn marker_byte_offset(input: &str, distinct_chars: usize) -> Option<usize> {
+    marker_window(input, distinct_chars).map(|(end, _)| {
+        input
+            .char_indices()
+            .nth(end)
+            .map_or(input.len(), |(offset, _)| offset)
+    })
+}
+
+// None when the stream never holds `distinct_chars` different characters in a row; the solvers
+// pass this through, so a stream without a marker reports as unsolved.
+fn marker_location(input: &str, distinct_chars: usize) -> Option<u32> {
+    marker_window(input, distinct_chars).map(|(end, _)| u32::try_from(end).unwrap_or(0))
 }
 
 #[must_use]
@@ -38,6 +73,59 @@ fn main() {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_marker_window() {
+        let input = advent_of_code::read_file("examples", 6);
+
+        let packet = marker_window(&input, 4);
+        assert_eq!(packet, Some((7, "jpqm")));
+        assert_eq!(packet.map(|(_, window)| window.len()), Some(4));
+
+        let message = marker_window(&input, 14);
+        assert_eq!(message, Some((19, "qmgbljsphdztnv")));
+        assert_eq!(message.map(|(_, window)| window.len()), Some(14));
+    }
+
+    #[test]
+    fn test_marker_window_short_input() {
+        assert_eq!(marker_window("abc", 4), None);
+        assert_eq!(marker_window("", 4), None);
+    }
+
+    #[test]
+    fn test_multibyte_input() {
+        let input = "ééé中中abcdxyz";
+
+        assert_eq!(marker_location(input, 4), Some(8));
+        assert_eq!(marker_window(input, 4), Some((8, "中abc")));
+        assert_eq!(marker_window(input, 5), Some((9, "中abcd")));
+
+        let offset = marker_byte_offset(input, 4);
+        assert_eq!(offset, Some(15));
+        assert_eq!(offset.map(|offset| &input[offset..]), Some("dxyz"));
+        assert_eq!(marker_byte_offset("abcd", 4), Some(4));
+        assert_eq!(marker_byte_offset("aaaa", 4), None);
+    }
+
+    #[test]
+    fn test_all_markers() {
+        assert_eq!(all_markers("aabcddddefgghh", 4), vec![1, 7]);
+        assert_eq!(all_markers("aaaa", 4), Vec::<u32>::new());
+
+        let input = advent_of_code::read_file("examples", 6);
+        let markers = all_markers(&input, 4);
+        assert_eq!(markers.first(), Some(&3));
+        assert!(markers.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_no_marker() {
+        assert_eq!(marker_location("aaaa", 4), None);
+        assert_eq!(marker_location("abcabcabc", 4), None);
+        assert_eq!(part_one("aaaa"), None);
+        assert_eq!(part_two("abcdefghijklm"), None);
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 6);
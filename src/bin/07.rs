@@ -1,8 +1,8 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
-#[derive(Debug)]
-struct FileSystem {
-    folders: HashSet<String>,
+#[derive(Debug, Default)]
+struct Dir {
+    children: HashMap<String, Dir>,
     files: HashMap<String, u32>,
 }
 
@@ -10,31 +10,56 @@ const MAX_SMALL_FOLDER_SIZE: u32 = 100_000;
 const FILE_SYSTEM_SIZE: u32 = 70_000_000;
 const SPACE_NEEDED: u32 = 30_000_000;
 
-impl FileSystem {
-    fn new() -> FileSystem {
-        let folders = HashSet::new();
-        let files = HashMap::new();
-        FileSystem { folders, files }
+impl Dir {
+    fn subfolder_mut(&mut self, path: &[String]) -> &mut Dir {
+        path.iter().fold(self, |dir, name| {
+            dir.children.entry(name.to_string()).or_default()
+        })
     }
 
-    fn total_size(&self, folder_path: &String) -> u32 {
-        self.files
-            .iter()
-            .map(|(path, size)| {
-                if path.starts_with(folder_path) {
-                    size
-                } else {
-                    &0
-                }
-            })
-            .sum()
+    fn total_size(&self) -> u32 {
+        self.files.values().sum::<u32>() + self.children.values().map(Dir::total_size).sum::<u32>()
+    }
+
+    fn collect_folder_sizes<'a>(&'a self, sizes: &mut Vec<(&'a Dir, u32)>) -> u32 {
+        let size = self.files.values().sum::<u32>()
+            + self
+                .children
+                .values()
+                .map(|child| child.collect_folder_sizes(sizes))
+                .sum::<u32>();
+        sizes.push((self, size));
+        size
+    }
+
+    // sizes of every folder below this one, not including the folder itself
+    fn folder_sizes(&self) -> Vec<(&Dir, u32)> {
+        let mut sizes = Vec::new();
+        for child in self.children.values() {
+            child.collect_folder_sizes(&mut sizes);
+        }
+        sizes
+    }
+}
+
+#[derive(Debug)]
+struct FileSystem {
+    root: Dir,
+}
+
+impl FileSystem {
+    fn new() -> FileSystem {
+        FileSystem {
+            root: Dir::default(),
+        }
     }
 
     fn total_size_of_small_directories(&self) -> u32 {
-        self.folders
+        self.root
+            .folder_sizes()
             .iter()
-            .map(|f| {
-                let size = self.total_size(f);
+            .map(|(_, size)| {
+                let size = *size;
                 if size <= MAX_SMALL_FOLDER_SIZE {
                     size
                 } else {
@@ -44,22 +69,20 @@ impl FileSystem {
             .sum()
     }
 
-    fn deletion_candidates(&self) -> HashMap<String, u32> {
-        let occupied: u32 = self.files.values().sum();
+    fn deletion_candidates(&self) -> Vec<u32> {
+        let occupied = self.root.total_size();
         let free_space_needed = SPACE_NEEDED - (FILE_SYSTEM_SIZE - occupied);
 
-        let mut candidates = HashMap::new();
-        for folder in &self.folders {
-            let size = self.total_size(folder);
-            if size >= free_space_needed {
-                candidates.insert(folder.to_string(), size);
-            };
-        }
-        candidates
+        self.root
+            .folder_sizes()
+            .into_iter()
+            .map(|(_, size)| size)
+            .filter(|size| *size >= free_space_needed)
+            .collect()
     }
 
     fn smallest_deletion_candidate_size(&self) -> Option<u32> {
-        self.deletion_candidates().values().min().copied()
+        self.deletion_candidates().into_iter().min()
     }
 }
 
@@ -75,22 +98,20 @@ fn read_file_system(input: &str) -> FileSystem {
             path.pop();
         } else if line == "$ ls" {
             continue;
-        } else if line[..5].to_string() == "$ cd " {
-            let subfolder = line[5..].to_string();
-            path.push(subfolder);
-            fs.folders.insert(path.join("/"));
-        } else if line[..4].to_string() == "dir" {
-            let subfolder = line[4..].to_string();
-            path.push(subfolder);
-            fs.folders.insert(path.join("/"));
-            path.pop();
-        } else {
-            let parts: Vec<&str> = line.split(' ').collect();
-            let filename = parts[1].to_string();
-            let filesize = parts[0].parse::<u32>().unwrap_or(0);
-            path.push(filename);
-            fs.files.insert(path.join("/"), filesize);
+        } else if let Some(subfolder) = line.strip_prefix("$ cd ") {
+            path.push(subfolder.to_string());
+            fs.root.subfolder_mut(&path);
+        } else if let Some(subfolder) = line.strip_prefix("dir ") {
+            path.push(subfolder.to_string());
+            fs.root.subfolder_mut(&path);
             path.pop();
+        } else if let Some((filesize, filename)) = line.split_once(' ') {
+            if let Ok(filesize) = filesize.parse::<u32>() {
+                fs.root
+                    .subfolder_mut(&path)
+                    .files
+                    .insert(filename.to_string(), filesize);
+            }
         }
     }
 
@@ -117,16 +138,116 @@ fn main() {
 mod tests {
     use super::*;
 
+    fn subfolder<'a>(dir: &'a Dir, path: &[&str]) -> Option<&'a Dir> {
+        path.iter()
+            .try_fold(dir, |dir, name| dir.children.get(*name))
+    }
+
     #[test]
     fn test_read_file_system() {
         let input = advent_of_code::read_file("examples", 7);
         let fs = read_file_system(&input);
 
-        assert_eq!(fs.folders.contains("a"), true);
-        assert_eq!(fs.folders.contains("d"), true);
-        assert_eq!(fs.folders.contains("a/e"), true);
+        assert!(subfolder(&fs.root, &["a"]).is_some());
+        assert!(subfolder(&fs.root, &["d"]).is_some());
+        assert!(subfolder(&fs.root, &["a", "e"]).is_some());
+
+        assert_eq!(
+            subfolder(&fs.root, &["d"])
+                .and_then(|d| d.files.get("d.ext"))
+                .copied(),
+            Some(5626152)
+        );
+        assert_eq!(fs.root.total_size(), 48381165);
+    }
+
+    #[test]
+    fn test_folder_sizes_match_total_size() {
+        let input = advent_of_code::read_file("examples", 7);
+        let fs = read_file_system(&input);
+        let sizes = fs.root.folder_sizes();
+
+        assert_eq!(sizes.len(), 3);
+        for (folder, size) in &sizes {
+            assert_eq!(folder.total_size(), *size);
+        }
+        assert!(sizes
+            .iter()
+            .all(|(folder, _)| !std::ptr::eq(*folder, &fs.root)));
+        assert_eq!(sizes.iter().map(|(_, size)| *size).max(), Some(24933642));
+    }
+
+    #[test]
+    fn test_duplicate_folder_names() {
+        let input = concat![
+            "$ cd /\n",
+            "$ ls\n",
+            "dir a\n",
+            "dir b\n",
+            "$ cd a\n",
+            "$ ls\n",
+            "dir x\n",
+            "$ cd x\n",
+            "$ ls\n",
+            "100 one.txt\n",
+            "$ cd ..\n",
+            "$ cd ..\n",
+            "$ cd b\n",
+            "$ ls\n",
+            "dir x\n",
+            "$ cd x\n",
+            "$ ls\n",
+            "20000 one.txt\n",
+        ];
+        let fs = read_file_system(input);
+
+        assert_eq!(
+            subfolder(&fs.root, &["a", "x"]).map(Dir::total_size),
+            Some(100)
+        );
+        assert_eq!(
+            subfolder(&fs.root, &["b", "x"]).map(Dir::total_size),
+            Some(20000)
+        );
+        assert_eq!(fs.root.total_size(), 20100);
+        assert_eq!(
+            fs.total_size_of_small_directories(),
+            100 + 100 + 20000 + 20000
+        );
+    }
+
+    #[test]
+    fn test_names_with_spaces() {
+        let input = concat![
+            "$ cd /\n",
+            "$ ls\n",
+            "dir my docs\n",
+            "$ cd my docs\n",
+            "$ ls\n",
+            "4096 my report.txt\n",
+            "12 notes\n",
+        ];
+        let fs = read_file_system(input);
+        let docs = subfolder(&fs.root, &["my docs"]);
+
+        assert_eq!(
+            docs.and_then(|d| d.files.get("my report.txt")).copied(),
+            Some(4096)
+        );
+        assert_eq!(docs.and_then(|d| d.files.get("notes")).copied(), Some(12));
+        assert_eq!(docs.map(Dir::total_size), Some(4108));
+    }
+
+    #[test]
+    fn test_short_and_dir_lines() {
+        let input = concat!["$ cd /\n", "\n", "$ ls\n", "ab\n", "dir e\n", "14 i\n",];
+        let fs = read_file_system(input);
 
-        assert_eq!(fs.files.get("d/d.ext").map(|v| *v as u32), Some(5626152));
+        assert!(subfolder(&fs.root, &["e"]).is_some());
+        assert!(!fs.root.files.contains_key("e"));
+        assert_eq!(fs.root.files.len(), 1);
+        assert_eq!(fs.root.files.get("i").copied(), Some(14));
+        assert_eq!(fs.root.folder_sizes().len(), 1);
     }
 
     #[test]
@@ -1,10 +1,11 @@
 use std::{
+    cmp::Reverse,
     collections::{HashMap, HashSet},
     ops::Add,
 };
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-struct Point {
+pub struct Point {
     x: i32,
     y: i32,
 }
@@ -29,47 +30,57 @@ fn compass() -> Vec<Point> {
     vec![UP, RIGHT, DOWN, LEFT]
 }
 
+#[derive(Debug, PartialEq)]
+struct Forest {
+    trees: HashMap<Point, u32>,
+    width: i32,
+    height: i32,
+}
+
+impl Forest {
+    fn get(&self, x: i32, y: i32) -> Option<u32> {
+        self.trees.get(&Point { x, y }).copied()
+    }
+
+    fn dimensions(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct TreeInfo {
     location: Point,
-    visible: bool,
     scenic_score: u32,
 }
 
 impl TreeInfo {
-    fn from_tree(location: Point, forest: &HashMap<Point, u32>) -> TreeInfo {
-        let mut visible = false;
+    fn from_tree(location: Point, forest: &Forest) -> TreeInfo {
         let mut scenic_score = 1;
-        let height = forest.get(&location).unwrap_or(&0);
+        let height = forest.get(location.x, location.y).unwrap_or(0);
 
         for direction in compass() {
-            let mut target = location;
+            let mut target = location + direction;
             let mut distance: u32 = 0;
 
-            loop {
-                target = target + direction;
-                if !forest.contains_key(&target) {
-                    visible = true;
-                    break;
-                }
+            while let Some(other) = forest.get(target.x, target.y) {
                 distance += 1;
-                if forest.get(&target).unwrap_or(&0) >= height {
+                if other >= height {
                     break;
                 }
+                target = target + direction;
             }
             scenic_score *= distance;
         }
 
         TreeInfo {
             location,
-            visible,
             scenic_score,
         }
     }
 }
 
-fn read_forest(input: &str) -> HashMap<Point, u32> {
-    let mut forest = HashMap::new();
+fn read_forest(input: &str) -> Forest {
+    let mut trees = HashMap::new();
 
     for (y, line) in input.lines().enumerate() {
         for (x, ch) in line.chars().enumerate() {
@@ -78,17 +89,23 @@ fn read_forest(input: &str) -> HashMap<Point, u32> {
                 y: i32::try_from(y).unwrap_or(0),
             };
             let height = ch.to_digit(10).unwrap_or(0);
-            forest.insert(pt, height);
+            trees.insert(pt, height);
         }
     }
 
-    forest
+    let width = trees.keys().map(|pt| pt.x + 1).max().unwrap_or(0);
+    let height = trees.keys().map(|pt| pt.y + 1).max().unwrap_or(0);
+    Forest {
+        trees,
+        width,
+        height,
+    }
 }
 
-fn trees_in_forest(forest: &HashMap<Point, u32>) -> HashSet<TreeInfo> {
+fn trees_in_forest(forest: &Forest) -> HashSet<TreeInfo> {
     let mut trees = HashSet::new();
 
-    for location in forest.keys() {
+    for location in forest.trees.keys() {
         let info = TreeInfo::from_tree(*location, forest);
         trees.insert(info);
     }
@@ -96,20 +113,117 @@ fn trees_in_forest(forest: &HashMap<Point, u32>) -> HashSet<TreeInfo> {
     trees
 }
 
+fn sweep(forest: &Forest, line: impl Iterator<Item = Point>, visible: &mut HashSet<Point>) {
+    let mut tallest: Option<u32> = None;
+    for pt in line {
+        let height = forest.get(pt.x, pt.y).unwrap_or(0);
+        if tallest.is_none_or(|tallest| height > tallest) {
+            visible.insert(pt);
+            tallest = Some(height);
+        }
+    }
+}
+
+fn visible_trees(forest: &Forest) -> HashSet<Point> {
+    let (width, height) = forest.dimensions();
+    let mut visible = HashSet::new();
+
+    for y in 0..height {
+        sweep(forest, (0..width).map(|x| Point { x, y }), &mut visible);
+        sweep(
+            forest,
+            (0..width).rev().map(|x| Point { x, y }),
+            &mut visible,
+        );
+    }
+    for x in 0..width {
+        sweep(forest, (0..height).map(|y| Point { x, y }), &mut visible);
+        sweep(
+            forest,
+            (0..height).rev().map(|y| Point { x, y }),
+            &mut visible,
+        );
+    }
+
+    visible
+}
+
+fn viewing_distances(heights: &[u32]) -> Vec<u32> {
+    let mut distances = Vec::new();
+    let mut taller: Vec<usize> = Vec::new();
+
+    for (ix, height) in heights.iter().enumerate() {
+        while taller.last().is_some_and(|top| heights[*top] < *height) {
+            taller.pop();
+        }
+        let distance = ix - taller.last().copied().unwrap_or(0);
+        distances.push(u32::try_from(distance).unwrap_or(0));
+        taller.push(ix);
+    }
+
+    distances
+}
+
+fn viewing_distances_both_ways(heights: &[u32]) -> Vec<u32> {
+    let forwards = viewing_distances(heights);
+    let reversed: Vec<u32> = heights.iter().rev().copied().collect();
+    let backwards = viewing_distances(&reversed);
+
+    forwards
+        .iter()
+        .zip(backwards.iter().rev())
+        .map(|(forward, backward)| forward * backward)
+        .collect()
+}
+
+fn scenic_scores_fast(forest: &Forest) -> HashMap<Point, u32> {
+    let (width, height) = forest.dimensions();
+    let mut scores = HashMap::new();
+
+    for y in 0..height {
+        let row: Vec<Point> = (0..width).map(|x| Point { x, y }).collect();
+        let heights: Vec<u32> = row
+            .iter()
+            .map(|pt| forest.get(pt.x, pt.y).unwrap_or(0))
+            .collect();
+        for (pt, score) in row.iter().zip(viewing_distances_both_ways(&heights)) {
+            scores.insert(*pt, score);
+        }
+    }
+
+    for x in 0..width {
+        let column: Vec<Point> = (0..height).map(|y| Point { x, y }).collect();
+        let heights: Vec<u32> = column
+            .iter()
+            .map(|pt| forest.get(pt.x, pt.y).unwrap_or(0))
+            .collect();
+        for (pt, score) in column.iter().zip(viewing_distances_both_ways(&heights)) {
+            scores.entry(*pt).and_modify(|total| *total *= score);
+        }
+    }
+
+    scores
+}
+
 #[must_use]
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn best_scenic_tree(input: &str) -> Option<(Point, u32)> {
     let forest = read_forest(input);
-    let trees = trees_in_forest(&forest);
+    trees_in_forest(&forest)
+        .iter()
+        .min_by_key(|tree| (Reverse(tree.scenic_score), tree.location.y, tree.location.x))
+        .map(|tree| (tree.location, tree.scenic_score))
+}
 
-    Some(trees.iter().map(|tree| u32::from(tree.visible)).sum())
+#[must_use]
+pub fn part_one(input: &str) -> Option<u32> {
+    let forest = read_forest(input);
+    u32::try_from(visible_trees(&forest).len()).ok()
 }
 
 #[must_use]
 pub fn part_two(input: &str) -> Option<u32> {
     let forest = read_forest(input);
-    let trees = trees_in_forest(&forest);
-
-    trees.iter().map(|tree| tree.scenic_score).max()
+    scenic_scores_fast(&forest).into_values().max()
 }
 
 fn main() {
@@ -127,11 +241,78 @@ mod tests {
         let input = advent_of_code::read_file("examples", 8);
         let forest = read_forest(&input);
 
-        assert_eq!(forest.len(), 25);
-        assert_eq!(forest.get(&Point { x: 0, y: 0 }), Some(&3));
-        assert_eq!(forest.get(&Point { x: 3, y: 0 }), Some(&7));
-        assert_eq!(forest.get(&Point { x: 1, y: 2 }), Some(&5));
-        assert_eq!(forest.get(&Point { x: 6, y: 2 }), None);
+        assert_eq!(forest.trees.len(), 25);
+        assert_eq!(forest.get(0, 0), Some(3));
+        assert_eq!(forest.get(3, 0), Some(7));
+        assert_eq!(forest.get(1, 2), Some(5));
+        assert_eq!(forest.get(6, 2), None);
+        assert_eq!(forest.get(-1, 0), None);
+    }
+
+    #[test]
+    fn test_forest_dimensions() {
+        let input = advent_of_code::read_file("examples", 8);
+        assert_eq!(read_forest(&input).dimensions(), (5, 5));
+        assert_eq!(read_forest("123\n456").dimensions(), (3, 2));
+    }
+
+    fn visible_by_walking(location: Point, forest: &Forest) -> bool {
+        let height = forest.get(location.x, location.y).unwrap_or(0);
+        compass().iter().any(|direction| {
+            let mut target = location + *direction;
+            while let Some(other) = forest.get(target.x, target.y) {
+                if other >= height {
+                    return false;
+                }
+                target = target + *direction;
+            }
+            true
+        })
+    }
+
+    #[test]
+    fn test_visible_trees() {
+        let input = advent_of_code::read_file("examples", 8);
+        let forest = read_forest(&input);
+        let visible = visible_trees(&forest);
+
+        let walked: HashSet<Point> = forest
+            .trees
+            .keys()
+            .filter(|location| visible_by_walking(**location, &forest))
+            .copied()
+            .collect();
+        assert_eq!(visible, walked);
+        assert_eq!(visible.len(), 21);
+    }
+
+    #[test]
+    fn test_viewing_distances() {
+        assert_eq!(viewing_distances(&[3, 3, 5, 4, 9]), vec![0, 1, 2, 1, 4]);
+    }
+
+    #[test]
+    fn test_scenic_scores_fast() {
+        let input = advent_of_code::read_file("examples", 8);
+        let forest = read_forest(&input);
+        let scores = scenic_scores_fast(&forest);
+
+        assert_eq!(scores.len(), forest.trees.len());
+        for tree in trees_in_forest(&forest) {
+            assert_eq!(scores.get(&tree.location), Some(&tree.scenic_score));
+        }
+        assert_eq!(scores.values().max(), Some(&8));
+    }
+
+    #[test]
+    fn test_best_scenic_tree() {
+        let input = advent_of_code::read_file("examples", 8);
+        assert_eq!(best_scenic_tree(&input), Some((Point { x: 2, y: 3 }, 8)));
+        assert_eq!(
+            best_scenic_tree("000\n000\n000"),
+            Some((Point { x: 1, y: 1 }, 1))
+        );
+        assert_eq!(best_scenic_tree("00\n00"), Some((Point { x: 0, y: 0 }, 0)));
     }
 
     #[test]
@@ -7,27 +7,47 @@ enum Direction {
     Right,
     Down,
     Left,
+    UpRight,
+    DownRight,
+    DownLeft,
+    UpLeft,
+    Forward,
+    Back,
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-struct Point {
-    x: i32,
-    y: i32,
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
 }
 
 impl Point {
+    fn new(x: i32, y: i32) -> Self {
+        Self::new_3d(x, y, 0)
+    }
+
+    fn new_3d(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
     fn neighbour_in_direction(self, direction: &Direction) -> Self {
         Self {
             x: match direction {
-                Direction::Left => self.x - 1,
-                Direction::Right => self.x + 1,
+                Direction::Left | Direction::DownLeft | Direction::UpLeft => self.x - 1,
+                Direction::Right | Direction::UpRight | Direction::DownRight => self.x + 1,
                 _ => self.x,
             },
             y: match direction {
-                Direction::Up => self.y + 1,
-                Direction::Down => self.y - 1,
+                Direction::Up | Direction::UpRight | Direction::UpLeft => self.y + 1,
+                Direction::Down | Direction::DownRight | Direction::DownLeft => self.y - 1,
                 _ => self.y,
             },
+            z: match direction {
+                Direction::Forward => self.z + 1,
+                Direction::Back => self.z - 1,
+                _ => self.z,
+            },
         }
     }
 
@@ -35,8 +55,9 @@ impl Point {
         let candidate = Self {
             x: self.x + (other.x - self.x).signum(),
             y: self.y + (other.y - self.y).signum(),
+            z: self.z + (other.z - self.z).signum(),
         };
-        if candidate.x == other.x && candidate.y == other.y {
+        if candidate == other {
             self
         } else {
             candidate
@@ -70,6 +91,12 @@ impl FromStr for Instruction {
                     Some(&"D") => Ok(Direction::Down),
                     Some(&"L") => Ok(Direction::Left),
                     Some(&"R") => Ok(Direction::Right),
+                    Some(&"UR") => Ok(Direction::UpRight),
+                    Some(&"DR") => Ok(Direction::DownRight),
+                    Some(&"DL") => Ok(Direction::DownLeft),
+                    Some(&"UL") => Ok(Direction::UpLeft),
+                    Some(&"F") => Ok(Direction::Forward),
+                    Some(&"B") => Ok(Direction::Back),
                     _ => Err(ParseInstructionError),
                 }?;
                 Ok(Instruction { direction, steps })
@@ -88,7 +115,7 @@ impl Rope {
         let mut knots = Vec::new();
 
         for _ in 0..len {
-            knots.push(Point { x: 0, y: 0 });
+            knots.push(Point::new(0, 0));
         }
 
         Rope { knots }
@@ -108,15 +135,27 @@ impl Rope {
         Rope { knots }
     }
 
+    fn head(&self) -> Point {
+        *self.knots.first().unwrap_or(&Point::new(0, 0))
+    }
+
     fn tail(&self) -> Point {
-        *self.knots.last().unwrap_or(&Point { x: 0, y: 0 })
+        *self.knots.last().unwrap_or(&Point::new(0, 0))
     }
 }
 
-fn tail_visits(input: &str, knots: usize) -> usize {
-    let mut rope = Rope::new(knots);
+#[must_use]
+pub fn tail_visited_positions(input: &str, knots: usize) -> HashSet<Point> {
     let mut visited: HashSet<Point> = HashSet::new();
 
+    // a rope needs a separate head and tail before the tail can visit anywhere
+    if knots < 2 {
+        return visited;
+    }
+
+    let mut rope = Rope::new(knots);
+    visited.insert(rope.tail());
+
     for line in input.lines() {
         match line.parse::<Instruction>() {
             Err(_) => {}
@@ -129,7 +168,49 @@ fn tail_visits(input: &str, knots: usize) -> usize {
         };
     }
 
-    visited.len()
+    visited
+}
+
+#[must_use]
+pub fn tail_visits(input: &str, knots: usize) -> usize {
+    tail_visited_positions(input, knots).len()
+}
+
+#[must_use]
+pub fn bounding_box(points: &HashSet<Point>) -> (Point, Point) {
+    let mut points = points.iter();
+    let Some(first) = points.next() else {
+        return (Point::new(0, 0), Point::new(0, 0));
+    };
+
+    points.fold((*first, *first), |(min, max), pt| {
+        (
+            Point::new_3d(min.x.min(pt.x), min.y.min(pt.y), min.z.min(pt.z)),
+            Point::new_3d(max.x.max(pt.x), max.y.max(pt.y), max.z.max(pt.z)),
+        )
+    })
+}
+
+#[must_use]
+pub fn trace(input: &str, knots: usize) -> Vec<(Point, Point)> {
+    let mut positions = Vec::new();
+
+    if knots < 2 {
+        return positions;
+    }
+
+    let mut rope = Rope::new(knots);
+
+    for line in input.lines() {
+        if let Ok(instruction) = line.parse::<Instruction>() {
+            for _ in 0..instruction.steps {
+                rope = rope.execute_step(&instruction.direction);
+            }
+            positions.push((rope.head(), rope.tail()));
+        }
+    }
+
+    positions
 }
 
 #[must_use]
@@ -154,25 +235,19 @@ mod tests {
 
     #[test]
     fn test_point_follow() {
-        assert_eq!(
-            Point { x: 1, y: 1 }.follow(Point { x: 1, y: 3 }),
-            Point { x: 1, y: 2 }
-        );
+        assert_eq!(Point::new(1, 1).follow(Point::new(1, 3)), Point::new(1, 2));
     }
 
     #[test]
     fn test_point_follow_but_dont_overlap() {
-        assert_eq!(
-            Point { x: 1, y: 1 }.follow(Point { x: 1, y: 2 }),
-            Point { x: 1, y: 1 }
-        );
+        assert_eq!(Point::new(1, 1).follow(Point::new(1, 2)), Point::new(1, 1));
     }
 
     #[test]
     fn test_point_neighbour_in_direction() {
         assert_eq!(
-            Point { x: 2, y: 3 }.neighbour_in_direction(&Direction::Up),
-            Point { x: 2, y: 4 }
+            Point::new(2, 3).neighbour_in_direction(&Direction::Up),
+            Point::new(2, 4)
         );
     }
 
@@ -187,42 +262,175 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_parse_instruction_diagonal() {
+        assert_eq!(
+            "UR 3".parse(),
+            Ok(Instruction {
+                direction: Direction::UpRight,
+                steps: 3
+            }),
+        );
+        assert_eq!(
+            "DL 2".parse(),
+            Ok(Instruction {
+                direction: Direction::DownLeft,
+                steps: 2
+            }),
+        );
+    }
+
+    #[test]
+    fn test_point_neighbour_in_diagonal_direction() {
+        assert_eq!(
+            Point::new(2, 3).neighbour_in_direction(&Direction::UpLeft),
+            Point::new(1, 4)
+        );
+        assert_eq!(
+            Point::new(2, 3).neighbour_in_direction(&Direction::DownRight),
+            Point::new(3, 2)
+        );
+    }
+
+    #[test]
+    fn test_rope_diagonal() {
+        let input = "UR 3\nDL 2";
+        assert_eq!(
+            trace(input, 2),
+            vec![
+                (Point::new(3, 3), Point::new(2, 2)),
+                (Point::new(1, 1), Point::new(2, 2)),
+            ]
+        );
+        assert_eq!(tail_visits(input, 2), 3);
+    }
+
     #[test]
     fn test_new_rope() {
         let rope = Rope::new(2);
-        assert_eq!(rope.knots, vec![Point { x: 0, y: 0 }, Point { x: 0, y: 0 }]);
+        assert_eq!(rope.knots, vec![Point::new(0, 0), Point::new(0, 0)]);
     }
 
     #[test]
     fn test_step_rope_tail_doesnt_overlap() {
         let before = Rope {
-            knots: vec![Point { x: 0, y: 0 }, Point { x: 0, y: 0 }],
+            knots: vec![Point::new(0, 0), Point::new(0, 0)],
         };
         assert_eq!(
             before.execute_step(&Direction::Right).tail(),
-            Point { x: 0, y: 0 }
+            Point::new(0, 0)
         );
     }
 
     #[test]
     fn test_step_rope_tail_follows() {
         let before = Rope {
-            knots: vec![Point { x: 1, y: 2 }, Point { x: 1, y: 1 }],
+            knots: vec![Point::new(1, 2), Point::new(1, 1)],
         };
-        assert_eq!(
-            before.execute_step(&Direction::Up).tail(),
-            Point { x: 1, y: 2 }
-        );
+        assert_eq!(before.execute_step(&Direction::Up).tail(), Point::new(1, 2));
     }
 
     #[test]
     fn test_step_rope_tail_follows_diagonally() {
         let before = Rope {
-            knots: vec![Point { x: 2, y: 2 }, Point { x: 1, y: 1 }],
+            knots: vec![Point::new(2, 2), Point::new(1, 1)],
         };
         assert_eq!(
             before.execute_step(&Direction::Right).tail(),
-            Point { x: 2, y: 2 }
+            Point::new(2, 2)
+        );
+    }
+
+    #[test]
+    fn test_point_follow_3d() {
+        assert_eq!(
+            Point::new_3d(0, 0, 0).follow(Point::new_3d(1, 1, 1)),
+            Point::new_3d(0, 0, 0)
+        );
+        assert_eq!(
+            Point::new_3d(0, 0, 0).follow(Point::new_3d(1, 1, 2)),
+            Point::new_3d(1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction_3d() {
+        assert_eq!(
+            "F 2".parse(),
+            Ok(Instruction {
+                direction: Direction::Forward,
+                steps: 2
+            }),
+        );
+        assert_eq!(
+            "B 1".parse(),
+            Ok(Instruction {
+                direction: Direction::Back,
+                steps: 1
+            }),
+        );
+    }
+
+    #[test]
+    fn test_rope_3d() {
+        let positions = trace("F 2\nU 1\nR 1\nF 1\nB 3", 2);
+        assert_eq!(
+            positions,
+            vec![
+                (Point::new_3d(0, 0, 2), Point::new_3d(0, 0, 1)),
+                (Point::new_3d(0, 1, 2), Point::new_3d(0, 0, 1)),
+                (Point::new_3d(1, 1, 2), Point::new_3d(0, 0, 1)),
+                (Point::new_3d(1, 1, 3), Point::new_3d(1, 1, 2)),
+                (Point::new_3d(1, 1, 0), Point::new_3d(1, 1, 1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let input = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2";
+        let positions = trace(input, 2);
+
+        assert_eq!(positions.len(), 8);
+        assert_eq!(positions[0], (Point::new(4, 0), Point::new(3, 0)));
+        assert_eq!(positions[1], (Point::new(4, 4), Point::new(4, 3)));
+        assert_eq!(positions[7], (Point::new(2, 2), Point::new(1, 2)));
+    }
+
+    #[test]
+    fn test_trace_too_few_knots() {
+        let input = "R 4\nU 4";
+        assert_eq!(trace(input, 0), Vec::new());
+        assert_eq!(trace(input, 1), Vec::new());
+    }
+
+    #[test]
+    fn test_tail_visits() {
+        let input = advent_of_code::read_file("examples", 9);
+        let counts: Vec<usize> = [0, 1, 2, 3, 10, 20]
+            .iter()
+            .map(|&knots| tail_visits(&input, knots))
+            .collect();
+        assert_eq!(counts, vec![0, 0, 88, 80, 36, 4]);
+    }
+
+    #[test]
+    fn test_tail_visited_positions() {
+        let input = advent_of_code::read_file("examples", 9);
+        for knots in [2, 3, 10] {
+            let visited = tail_visited_positions(&input, knots);
+            assert_eq!(visited.len(), tail_visits(&input, knots));
+            assert!(visited.contains(&Point::new(0, 0)));
+        }
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let visited = tail_visited_positions("R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2", 2);
+        assert_eq!(bounding_box(&visited), (Point::new(0, 0), Point::new(4, 4)));
+        assert_eq!(
+            bounding_box(&HashSet::new()),
+            (Point::new(0, 0), Point::new(0, 0))
         );
     }
 
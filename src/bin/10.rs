@@ -40,6 +40,16 @@ fn read_program(input: &str) -> Result<Vec<Instruction>, ParseInstructionError>
     Ok(program)
 }
 
+fn cycle_count(program: &[Instruction]) -> usize {
+    program
+        .iter()
+        .map(|instruction| match instruction {
+            Instruction::NoOp => 1,
+            Instruction::AddX(_) => 2,
+        })
+        .sum()
+}
+
 fn run_program(program: Vec<Instruction>) -> Vec<i32> {
     let mut x: i32 = 1;
     let mut values = Vec::new();
@@ -59,37 +69,114 @@ fn run_program(program: Vec<Instruction>) -> Vec<i32> {
     values
 }
 
-fn signal_strength(program_results: &[i32]) -> i32 {
-    (20..=220)
-        .step_by(40)
-        .map(|cycle| {
-            let register_value = program_results[cycle - 1];
+#[must_use]
+pub fn signal_strength_at(program_results: &[i32], cycles: &[usize]) -> i32 {
+    cycles
+        .iter()
+        .map(|&cycle| {
+            let register_value = cycle
+                .checked_sub(1)
+                .and_then(|ix| program_results.get(ix))
+                .unwrap_or(&0);
             register_value * i32::try_from(cycle).unwrap_or(0)
         })
         .sum()
 }
 
+fn signal_strength(program_results: &[i32]) -> i32 {
+    let cycles: Vec<usize> = (20..=220).step_by(40).collect();
+    signal_strength_at(program_results, &cycles)
+}
+
+const CRT_WIDTH: usize = 40;
+const CRT_HEIGHT: usize = 6;
+
+#[must_use]
+pub fn crt_pixels(program_results: &[i32]) -> [[bool; CRT_WIDTH]; CRT_HEIGHT] {
+    crt_pixels_with_sprite(program_results, 1)
+}
+
+fn crt_pixels_with_sprite(
+    program_results: &[i32],
+    sprite_half_width: i32,
+) -> [[bool; CRT_WIDTH]; CRT_HEIGHT] {
+    let mut pixels = [[false; CRT_WIDTH]; CRT_HEIGHT];
+
+    for (ix, x) in program_results
+        .iter()
+        .take(CRT_WIDTH * CRT_HEIGHT)
+        .enumerate()
+    {
+        let pixel = i32::try_from(ix % CRT_WIDTH).unwrap_or(0);
+        pixels[ix / CRT_WIDTH][ix % CRT_WIDTH] = (pixel - x).abs() <= sprite_half_width;
+    }
+
+    pixels
+}
+
 fn crt_image(program_results: &[i32]) -> String {
+    crt_image_with_sprite(program_results, 1)
+}
+
+#[must_use]
+pub fn crt_image_with_sprite(program_results: &[i32], sprite_half_width: i32) -> String {
     let mut image = String::new();
-    let mut line = String::new();
 
-    for (ix, x) in program_results.iter().enumerate() {
-        let pixel = i32::try_from(ix % 40).unwrap_or(0);
-        line.push(if *x - 1 == pixel || *x == pixel || *x + 1 == pixel {
-            '#'
-        } else {
-            '.'
-        });
-        if line.len() == 40 {
-            image.push_str(&line);
-            image.push('\n');
-            line = String::new();
-        };
+    for row in crt_pixels_with_sprite(program_results, sprite_half_width) {
+        for lit in row {
+            image.push(if lit { '#' } else { '.' });
+        }
+        image.push('\n');
     }
 
     image
 }
 
+const LETTER_WIDTH: usize = 5;
+
+// each glyph is four columns wide, stored row by row
+const FONT: [(char, &str); 16] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+#[must_use]
+pub fn decode_crt(image: &str) -> Option<String> {
+    let rows: Vec<&str> = image.lines().collect();
+    if rows.len() != CRT_HEIGHT || rows.iter().any(|row| row.len() != CRT_WIDTH) {
+        return None;
+    }
+
+    let mut letters = String::new();
+    for left in (0..CRT_WIDTH).step_by(LETTER_WIDTH) {
+        let glyph: String = rows
+            .iter()
+            .map(|row| row.get(left..left + LETTER_WIDTH - 1))
+            .collect::<Option<String>>()?;
+        if glyph.chars().all(|c| c == '.') {
+            continue;
+        }
+        let (letter, _) = FONT.iter().find(|(_, pattern)| *pattern == glyph)?;
+        letters.push(*letter);
+    }
+
+    Some(letters)
+}
+
 #[must_use]
 pub fn part_one(input: &str) -> Option<i32> {
     match read_program(input) {
@@ -102,7 +189,12 @@ pub fn part_one(input: &str) -> Option<i32> {
 pub fn part_two(input: &str) -> Option<String> {
     match read_program(input) {
         Err(_) => None,
-        Ok(program) => Some(crt_image(&run_program(program))),
+        // a program that stops early would leave part of the screen unrendered
+        Ok(program) if cycle_count(&program) < CRT_WIDTH * CRT_HEIGHT => None,
+        Ok(program) => {
+            let image = crt_image(&run_program(program));
+            Some(decode_crt(&image).unwrap_or(image))
+        }
     }
 }
 
@@ -159,6 +251,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::unwrap_or_default)]
     fn test_run_program_longer_example() {
         let input = advent_of_code::read_file("examples", 10);
         let program = read_program(&input).unwrap_or(Vec::new());
@@ -172,14 +265,137 @@ mod tests {
         assert_eq!(results[219], 18);
     }
 
+    #[test]
+    fn test_signal_strength_at_single_cycle() {
+        let input = advent_of_code::read_file("examples", 10);
+        let results = run_program(read_program(&input).unwrap_or_default());
+        assert_eq!(signal_strength_at(&results, &[20]), 420);
+        assert_eq!(signal_strength_at(&results, &[]), 0);
+    }
+
+    #[test]
+    fn test_signal_strength_at_out_of_range() {
+        let results = run_program(read_program("noop\naddx 3\naddx -5").unwrap_or_default());
+        assert_eq!(signal_strength_at(&results, &[4, 0, 100]), 16);
+    }
+
+    #[test]
+    fn test_cycle_count() {
+        let program = read_program("noop\naddx 3\naddx -5").unwrap_or_default();
+        assert_eq!(cycle_count(&program), 5);
+
+        let input = advent_of_code::read_file("examples", 10);
+        let program = read_program(&input).unwrap_or_default();
+        assert!(cycle_count(&program) >= CRT_WIDTH * CRT_HEIGHT);
+    }
+
+    #[test]
+    fn test_part_two_truncated_program() {
+        let input = advent_of_code::read_file("examples", 10);
+        let truncated: String = input
+            .lines()
+            .take(100)
+            .map(|line| format!("{line}\n"))
+            .collect();
+        assert_eq!(part_two(&truncated), None);
+    }
+
+    #[test]
+    fn test_crt_pixels() {
+        let input = advent_of_code::read_file("examples", 10);
+        let program = read_program(&input).unwrap_or_default();
+        let pixels = crt_pixels(&run_program(program));
+
+        assert_eq!(pixels.len(), 6);
+        assert!(pixels.iter().all(|row| row.len() == 40));
+        assert!(pixels[0][0]);
+        assert!(!pixels[0][2]);
+    }
+
+    #[test]
+    fn test_crt_image_with_narrow_sprite() {
+        let input = advent_of_code::read_file("examples", 10);
+        let results = run_program(read_program(&input).unwrap_or_default());
+        let narrow = crt_image_with_sprite(&results, 0);
+        let normal = crt_image_with_sprite(&results, 1);
+
+        assert_eq!(normal, crt_image(&results));
+        assert_eq!(narrow.lines().count(), 6);
+        assert!(narrow.matches('#').count() < normal.matches('#').count());
+        assert_eq!(
+            narrow.lines().next(),
+            Some(".#...#..#...#....#......................")
+        );
+    }
+
+    #[test]
+    fn test_decode_crt() {
+        let image = concat![
+            "#..#.####.#....#.....##.................\n",
+            "#..#.#....#....#....#..#................\n",
+            "####.###..#....#....#..#................\n",
+            "#..#.#....#....#....#..#................\n",
+            "#..#.#....#....#....#..#................\n",
+            "#..#.####.####.####..##.................\n",
+        ];
+        assert_eq!(decode_crt(image), Some("HELLO".to_string()));
+    }
+
+    #[test]
+    fn test_decode_crt_wrong_size() {
+        assert_eq!(decode_crt("#..#\n#..#\n####\n#..#\n#..#\n#..#\n"), None);
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 10);
         assert_eq!(part_one(&input), Some(13140));
     }
 
+    // builds a program which draws the given word, choosing the sprite position for
+    // each pair of pixels with one addx per pair
+    fn program_drawing(word: &str) -> String {
+        let mut pixels = Vec::new();
+        for row in 0..CRT_HEIGHT {
+            for letter in word.chars() {
+                let glyph = FONT
+                    .iter()
+                    .find(|(ch, _)| *ch == letter)
+                    .map_or("", |(_, glyph)| glyph);
+                pixels.extend(glyph.chars().skip(row * 4).take(4).map(|c| c == '#'));
+                pixels.push(false);
+            }
+            pixels.resize((row + 1) * CRT_WIDTH, false);
+        }
+
+        let mut x = 1;
+        let mut program = String::new();
+        for (ix, pair) in pixels.chunks(2).enumerate().skip(1) {
+            let col = i32::try_from((ix * 2) % CRT_WIDTH).unwrap_or_default();
+            let target = match (pair[0], pair[1]) {
+                (true, true) => col,
+                (true, false) => col - 1,
+                (false, true) => col + 2,
+                (false, false) => col + 10,
+            };
+            program.push_str(&format!("addx {}\n", target - x));
+            x = target;
+        }
+        program.push_str("addx 0\n");
+        program
+    }
+
+    #[test]
+    fn test_part_two_decodes_letters() {
+        assert_eq!(
+            part_two(&program_drawing("ZEBRAFLU")),
+            Some("ZEBRAFLU".to_string())
+        );
+    }
+
     #[test]
     fn test_part_two() {
+        // the example program doesn't spell out letters, so the raw image is returned
         let input = advent_of_code::read_file("examples", 10);
         let image = concat![
             "##..##..##..##..##..##..##..##..##..##..\n",
@@ -12,19 +12,44 @@ enum Operation {
     Add(u64),
     Multiply(u64),
     Square,
+    Subtract(u64),
+    Divide(u64),
+}
+
+#[derive(Debug, PartialEq)]
+struct WorryOverflowError;
+
+#[derive(Debug, PartialEq)]
+pub enum SimulationError {
+    WorryOverflow,
+    UnknownMonkey(usize),
+    NonModularOperation(usize),
+}
+
+impl From<WorryOverflowError> for SimulationError {
+    fn from(_: WorryOverflowError) -> Self {
+        SimulationError::WorryOverflow
+    }
 }
 
 impl Operation {
-    fn apply(&self, item: u64, strategy: &WorryManagementStrategy) -> u64 {
+    fn apply(
+        &self,
+        item: u64,
+        strategy: &WorryManagementStrategy,
+    ) -> Result<u64, WorryOverflowError> {
         let value = match self {
-            Operation::Add(operand) => item + operand,
-            Operation::Multiply(operand) => item * operand,
-            Operation::Square => item * item,
-        };
-        match strategy {
+            Operation::Add(operand) => item.checked_add(*operand),
+            Operation::Multiply(operand) => item.checked_mul(*operand),
+            Operation::Square => item.checked_mul(item),
+            Operation::Subtract(operand) => Some(item.saturating_sub(*operand)),
+            Operation::Divide(operand) => item.checked_div(*operand),
+        }
+        .ok_or(WorryOverflowError)?;
+        Ok(match strategy {
             WorryManagementStrategy::DivideByThree => value / 3,
             WorryManagementStrategy::Modulo(m) => value % m,
-        }
+        })
     }
 }
 
@@ -46,6 +71,8 @@ impl FromStr for Operation {
             match parts.first() {
                 Some(&"*") => Ok(Operation::Multiply(operand)),
                 Some(&"+") => Ok(Operation::Add(operand)),
+                Some(&"-") => Ok(Operation::Subtract(operand)),
+                Some(&"/") if operand != 0 => Ok(Operation::Divide(operand)),
                 _ => Err(ParseOperationError),
             }
         } else {
@@ -148,7 +175,33 @@ fn parse_monkeys(input: &str) -> Vec<Monkey> {
     monkeys
 }
 
-fn monkey_business(monkeys: &Vec<Monkey>, rounds: u64, part_two: bool) -> u64 {
+type MonkeyItems = HashMap<usize, VecDeque<u64>>;
+type InspectionCounts = HashMap<usize, u64>;
+
+fn simulate(
+    monkeys: &[Monkey],
+    rounds: u64,
+    part_two: bool,
+) -> Result<(MonkeyItems, InspectionCounts), SimulationError> {
+    for monkey in monkeys {
+        for target in [monkey.throw_if_true, monkey.throw_if_false] {
+            if !monkeys.iter().any(|other| other.id == target) {
+                return Err(SimulationError::UnknownMonkey(target));
+            }
+        }
+
+        // subtraction and division don't preserve remainders, so worry levels can't be
+        // reduced modulo the tests once either is in play
+        if part_two
+            && matches!(
+                monkey.operation,
+                Operation::Subtract(_) | Operation::Divide(_)
+            )
+        {
+            return Err(SimulationError::NonModularOperation(monkey.id));
+        }
+    }
+
     let mut items: HashMap<usize, VecDeque<u64>> = HashMap::new();
     let mut inspection_counts: HashMap<usize, u64> = HashMap::new();
     let mut mod_prod = 1;
@@ -169,9 +222,9 @@ fn monkey_business(monkeys: &Vec<Monkey>, rounds: u64, part_two: bool) -> u64 {
         for monkey in monkeys {
             // inspect and queue items for throwing
             let mut thrown: Vec<(usize, u64)> = Vec::new();
-            items.entry(monkey.id).and_modify(|inventory| {
+            if let Some(inventory) = items.get_mut(&monkey.id) {
                 while let Some(item) = inventory.pop_front() {
-                    let item = monkey.operation.apply(item, &strategy);
+                    let item = monkey.operation.apply(item, &strategy)?;
                     let target = if item % monkey.test == 0 {
                         monkey.throw_if_true
                     } else {
@@ -179,7 +232,7 @@ fn monkey_business(monkeys: &Vec<Monkey>, rounds: u64, part_two: bool) -> u64 {
                     };
                     thrown.push((target, item));
                 }
-            });
+            }
 
             // record the number of inspections
             let inspections = thrown.len() as u64;
@@ -191,38 +244,72 @@ fn monkey_business(monkeys: &Vec<Monkey>, rounds: u64, part_two: bool) -> u64 {
             // throw items to other monkeys
             for (target, item) in thrown {
                 items
-                    .entry(target)
-                    .and_modify(|inventory| inventory.push_back(item))
-                    .or_default();
+                    .get_mut(&target)
+                    .ok_or(SimulationError::UnknownMonkey(target))?
+                    .push_back(item);
             }
         }
     }
 
-    let (one, two): (u64, u64) =
-        inspection_counts
-            .values()
-            .fold((0, 0), |(biggest, big), count| {
-                if count > &biggest {
-                    (*count, biggest)
-                } else if count > &big {
-                    (biggest, *count)
-                } else {
-                    (biggest, big)
-                }
-            });
-    one * two
+    Ok((items, inspection_counts))
+}
+
+pub fn final_items(
+    input: &str,
+    rounds: u64,
+    part_two: bool,
+) -> Result<Vec<Vec<u64>>, SimulationError> {
+    let monkeys = parse_monkeys(input);
+    let (mut items, _) = simulate(&monkeys, rounds, part_two)?;
+    Ok(monkeys
+        .iter()
+        .map(|monkey| items.remove(&monkey.id).map(Vec::from).unwrap_or_default())
+        .collect())
+}
+
+fn inspection_counts(
+    monkeys: &[Monkey],
+    rounds: u64,
+    part_two: bool,
+) -> Result<Vec<(usize, u64)>, SimulationError> {
+    let (_, inspection_counts) = simulate(monkeys, rounds, part_two)?;
+    Ok(monkeys
+        .iter()
+        .map(|monkey| {
+            (
+                monkey.id,
+                inspection_counts.get(&monkey.id).copied().unwrap_or(0),
+            )
+        })
+        .collect())
+}
+
+/// # Errors
+///
+/// Will return `UnknownMonkey` if a monkey throws to an id missing from the input,
+/// `WorryOverflow` if an operation overflows a worry level, or `NonModularOperation`
+/// if `part_two` is set and a monkey's operation subtracts or divides.
+pub fn monkey_business(input: &str, rounds: u64, part_two: bool) -> Result<u64, SimulationError> {
+    let monkeys = parse_monkeys(input);
+    let mut counts: Vec<u64> = inspection_counts(&monkeys, rounds, part_two)?
+        .into_iter()
+        .map(|(_, count)| count)
+        .collect();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(counts
+        .first()
+        .zip(counts.get(1))
+        .map_or(0, |(one, two)| one * two))
 }
 
 #[must_use]
 pub fn part_one(input: &str) -> Option<u64> {
-    let monkeys = parse_monkeys(input);
-    Some(monkey_business(&monkeys, 20, false))
+    monkey_business(input, 20, false).ok()
 }
 
 #[must_use]
 pub fn part_two(input: &str) -> Option<u64> {
-    let monkeys = parse_monkeys(input);
-    Some(monkey_business(&monkeys, 10_000, true))
+    monkey_business(input, 10_000, true).ok()
 }
 
 fn main() {
@@ -250,6 +337,54 @@ mod tests {
         assert_eq!("new = old * old".parse(), Ok(Operation::Square),);
     }
 
+    #[test]
+    fn test_parse_operation_subtract() {
+        assert_eq!("new = old - 2".parse(), Ok(Operation::Subtract(2)),);
+    }
+
+    #[test]
+    fn test_parse_operation_divide() {
+        assert_eq!("new = old / 4".parse(), Ok(Operation::Divide(4)),);
+        assert_eq!(
+            "new = old / 0".parse::<Operation>(),
+            Err(ParseOperationError),
+        );
+    }
+
+    #[test]
+    fn test_apply_operation_subtract_and_divide() {
+        let strategy = WorryManagementStrategy::DivideByThree;
+        assert_eq!(Operation::Subtract(2).apply(11, &strategy), Ok(3));
+        assert_eq!(Operation::Subtract(2).apply(1, &strategy), Ok(0));
+        assert_eq!(Operation::Divide(4).apply(50, &strategy), Ok(4));
+        assert_eq!(Operation::Divide(4).apply(3, &strategy), Ok(0));
+    }
+
+    #[test]
+    fn test_apply_operation() {
+        let strategy = WorryManagementStrategy::DivideByThree;
+        assert_eq!(Operation::Add(5).apply(10, &strategy), Ok(5));
+        assert_eq!(Operation::Multiply(19).apply(79, &strategy), Ok(500));
+        assert_eq!(Operation::Square.apply(79, &strategy), Ok(2080));
+    }
+
+    #[test]
+    fn test_apply_operation_overflow() {
+        let strategy = WorryManagementStrategy::DivideByThree;
+        assert_eq!(
+            Operation::Multiply(u64::MAX / 2).apply(3, &strategy),
+            Err(WorryOverflowError)
+        );
+        assert_eq!(
+            Operation::Square.apply(u64::MAX / 2, &strategy),
+            Err(WorryOverflowError)
+        );
+        assert_eq!(
+            Operation::Add(u64::MAX).apply(1, &strategy),
+            Err(WorryOverflowError)
+        );
+    }
+
     #[test]
     fn test_parse_monkey() {
         let monkey_zero = Monkey {
@@ -318,6 +453,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_final_items() {
+        let input = advent_of_code::read_file("examples", 11);
+        let monkeys = parse_monkeys(&input);
+        let starting: usize = monkeys.iter().map(|m| m.starting_items.len()).sum();
+
+        let items = final_items(&input, 20, false);
+        assert_eq!(
+            items,
+            Ok(vec![
+                vec![10, 12, 14, 26, 34],
+                vec![245, 93, 53, 199, 115],
+                vec![],
+                vec![],
+            ])
+        );
+        assert_eq!(
+            items.map(|items| items.iter().map(Vec::len).sum()),
+            Ok(starting)
+        );
+    }
+
+    #[test]
+    fn test_inspection_counts() {
+        let input = advent_of_code::read_file("examples", 11);
+        let monkeys = parse_monkeys(&input);
+        assert_eq!(
+            inspection_counts(&monkeys, 20, false),
+            Ok(vec![(0, 101), (1, 95), (2, 7), (3, 105)])
+        );
+    }
+
+    #[test]
+    fn test_monkey_business_unknown_target() {
+        let input = advent_of_code::read_file("examples", 11);
+        let input = input.replace("If false: throw to monkey 1", "If false: throw to monkey 7");
+        assert_eq!(
+            monkey_business(&input, 20, false),
+            Err(SimulationError::UnknownMonkey(7))
+        );
+        assert_eq!(part_one(&input), None);
+    }
+
+    #[test]
+    fn test_monkey_business_non_modular_operation() {
+        let input = advent_of_code::read_file("examples", 11);
+        let input = input.replace("new = old + 6", "new = old - 6");
+        assert!(monkey_business(&input, 20, false).is_ok());
+        assert_eq!(
+            monkey_business(&input, 10_000, true),
+            Err(SimulationError::NonModularOperation(1))
+        );
+        assert_eq!(part_two(&input), None);
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 11);
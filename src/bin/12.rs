@@ -1,14 +1,21 @@
-use std::collections::{HashSet, VecDeque};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet, VecDeque};
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq)]
-enum ShortestPathType {
+pub enum ShortestPathType {
     EndToEnd,
     Hiking,
 }
 
 #[derive(Debug, PartialEq)]
-struct Grid {
+enum Connectivity {
+    Four,
+    Eight,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Grid {
     width: usize,
     heights: Vec<u32>,
     start: usize,
@@ -16,25 +23,78 @@ struct Grid {
 }
 
 impl Grid {
-    fn shortest_path(&self, path_type: &ShortestPathType) -> Option<u32> {
+    fn is_destination(&self, pos: usize, path_type: &ShortestPathType) -> bool {
+        match path_type {
+            ShortestPathType::EndToEnd => pos == self.start,
+            ShortestPathType::Hiking => self.heights[pos] == 0,
+        }
+    }
+
+    fn neighbours(&self, pos: usize, wrap: bool, connectivity: &Connectivity) -> Vec<usize> {
+        let min_height = self.heights[pos].saturating_sub(1);
+        let x = pos % self.width;
+        let mut neighbours = Vec::new();
+
+        if x != 0 {
+            neighbours.push(pos - 1);
+        }
+        if x + 1 != self.width {
+            neighbours.push(pos + 1);
+        }
+        if pos >= self.width {
+            neighbours.push(pos - self.width);
+        }
+        if pos + self.width < self.heights.len() {
+            neighbours.push(pos + self.width);
+        }
+        if connectivity == &Connectivity::Eight {
+            let above = pos >= self.width;
+            let below = pos + self.width < self.heights.len();
+            if x != 0 && above {
+                neighbours.push(pos - self.width - 1);
+            }
+            if x + 1 != self.width && above {
+                neighbours.push(pos - self.width + 1);
+            }
+            if x != 0 && below {
+                neighbours.push(pos + self.width - 1);
+            }
+            if x + 1 != self.width && below {
+                neighbours.push(pos + self.width + 1);
+            }
+        }
+        if wrap {
+            if x == 0 {
+                neighbours.push(pos + self.width - 1);
+            }
+            if x + 1 == self.width {
+                neighbours.push(pos + 1 - self.width);
+            }
+            if pos < self.width {
+                neighbours.push(pos + self.heights.len() - self.width);
+            }
+            if pos + self.width >= self.heights.len() {
+                neighbours.push(pos + self.width - self.heights.len());
+            }
+        }
+
+        neighbours.retain(|&neighbour| self.heights[neighbour] >= min_height);
+        neighbours
+    }
+
+    fn shortest_path(
+        &self,
+        path_type: &ShortestPathType,
+        wrap: bool,
+        connectivity: &Connectivity,
+    ) -> Option<u32> {
         let mut visited: HashSet<usize> = HashSet::new();
         let mut consider: VecDeque<(usize, u32)> = VecDeque::new();
         consider.push_back((self.goal, 0));
 
         while let Some((pos, steps)) = consider.pop_front() {
-            let height = self.heights[pos];
-
-            match path_type {
-                ShortestPathType::EndToEnd => {
-                    if pos == self.start {
-                        return Some(steps);
-                    }
-                }
-                ShortestPathType::Hiking => {
-                    if height == 0 {
-                        return Some(steps);
-                    }
-                }
+            if self.is_destination(pos, path_type) {
+                return Some(steps);
             }
 
             if visited.contains(&pos) {
@@ -43,39 +103,131 @@ impl Grid {
 
             visited.insert(pos);
 
-            let min_height = if height == 0 { 0 } else { height - 1 };
-            let x = pos % self.width;
+            for neighbour in self.neighbours(pos, wrap, connectivity) {
+                consider.push_back((neighbour, steps + 1));
+            }
+        }
 
-            if x != 0 {
-                let left = pos - 1;
-                if self.heights[left] >= min_height {
-                    consider.push_back((left, steps + 1));
-                }
+        None
+    }
+
+    fn distance_to_start(&self, pos: usize) -> u32 {
+        let (x, y) = (pos % self.width, pos / self.width);
+        let (start_x, start_y) = (self.start % self.width, self.start / self.width);
+        u32::try_from(x.abs_diff(start_x) + y.abs_diff(start_y)).unwrap_or(u32::MAX)
+    }
+
+    #[must_use]
+    pub fn shortest_path_astar(&self) -> Option<u32> {
+        let mut best: Vec<Option<u32>> = vec![None; self.heights.len()];
+        let mut consider: BinaryHeap<Reverse<(u32, u32, usize)>> = BinaryHeap::new();
+        best[self.goal] = Some(0);
+        consider.push(Reverse((self.distance_to_start(self.goal), 0, self.goal)));
+
+        while let Some(Reverse((_, steps, pos))) = consider.pop() {
+            if pos == self.start {
+                return Some(steps);
             }
-            if x + 1 != self.width {
-                let right = pos + 1;
-                if self.heights[right] >= min_height {
-                    consider.push_back((right, steps + 1));
+
+            if best[pos].is_some_and(|best| best < steps) {
+                continue;
+            }
+
+            for neighbour in self.neighbours(pos, false, &Connectivity::Four) {
+                let next = steps + 1;
+                if best[neighbour].is_none_or(|best| next < best) {
+                    best[neighbour] = Some(next);
+                    consider.push(Reverse((
+                        next + self.distance_to_start(neighbour),
+                        next,
+                        neighbour,
+                    )));
                 }
             }
-            if pos >= self.width {
-                let up = pos - self.width;
-                if self.heights[up] >= min_height {
-                    consider.push_back((up, steps + 1));
+        }
+
+        None
+    }
+
+    #[must_use]
+    pub fn shortest_path_route(&self, path_type: &ShortestPathType) -> Option<Vec<usize>> {
+        let mut previous: Vec<Option<usize>> = vec![None; self.heights.len()];
+        let mut visited: Vec<bool> = vec![false; self.heights.len()];
+        let mut consider: VecDeque<usize> = VecDeque::new();
+        visited[self.goal] = true;
+        consider.push_back(self.goal);
+
+        while let Some(pos) = consider.pop_front() {
+            if self.is_destination(pos, path_type) {
+                // the search runs backwards from the goal, so following the
+                // predecessors from here walks the route in its forward order
+                let mut route = vec![pos];
+                let mut current = pos;
+                while let Some(next) = previous[current] {
+                    route.push(next);
+                    current = next;
                 }
+                return Some(route);
             }
-            let down = pos + self.width;
-            if down < self.heights.len() && self.heights[down] >= min_height {
-                consider.push_back((down, steps + 1));
+
+            for neighbour in self.neighbours(pos, false, &Connectivity::Four) {
+                if !visited[neighbour] {
+                    visited[neighbour] = true;
+                    previous[neighbour] = Some(pos);
+                    consider.push_back(neighbour);
+                }
             }
         }
 
         None
     }
+
+    #[must_use]
+    pub fn count_shortest_paths(&self, path_type: &ShortestPathType) -> u64 {
+        let mut distances: Vec<Option<u32>> = vec![None; self.heights.len()];
+        let mut paths: Vec<u64> = vec![0; self.heights.len()];
+        let mut consider: VecDeque<usize> = VecDeque::new();
+        let mut shortest: Option<u32> = None;
+        let mut total = 0;
+
+        distances[self.goal] = Some(0);
+        paths[self.goal] = 1;
+        consider.push_back(self.goal);
+
+        while let Some(pos) = consider.pop_front() {
+            let steps = distances[pos].unwrap_or(0);
+
+            if shortest.is_some_and(|shortest| steps > shortest) {
+                break;
+            }
+
+            if self.is_destination(pos, path_type) {
+                shortest = Some(steps);
+                total += paths[pos];
+                continue;
+            }
+
+            for neighbour in self.neighbours(pos, false, &Connectivity::Four) {
+                match distances[neighbour] {
+                    None => {
+                        distances[neighbour] = Some(steps + 1);
+                        paths[neighbour] = paths[pos];
+                        consider.push_back(neighbour);
+                    }
+                    Some(distance) if distance == steps + 1 => {
+                        paths[neighbour] += paths[pos];
+                    }
+                    Some(_) => (),
+                }
+            }
+        }
+
+        total
+    }
 }
 
 #[derive(Debug, PartialEq)]
-struct ParseGridError;
+pub struct ParseGridError;
 
 impl FromStr for Grid {
     type Err = ParseGridError;
@@ -127,7 +279,7 @@ impl FromStr for Grid {
 #[must_use]
 pub fn part_one(input: &str) -> Option<u32> {
     if let Ok(grid) = input.parse::<Grid>() {
-        grid.shortest_path(&ShortestPathType::EndToEnd)
+        grid.shortest_path(&ShortestPathType::EndToEnd, false, &Connectivity::Four)
     } else {
         None
     }
@@ -136,7 +288,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 #[must_use]
 pub fn part_two(input: &str) -> Option<u32> {
     if let Ok(grid) = input.parse::<Grid>() {
-        grid.shortest_path(&ShortestPathType::Hiking)
+        grid.shortest_path(&ShortestPathType::Hiking, false, &Connectivity::Four)
     } else {
         None
     }
@@ -184,4 +336,125 @@ mod tests {
         let input = advent_of_code::read_file("examples", 12);
         assert_eq!(part_two(&input), Some(29));
     }
+
+    #[test]
+    fn test_shortest_path_wrapping() {
+        let grid = Grid {
+            width: 5,
+            heights: vec![0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            start: 0,
+            goal: 4,
+        };
+        assert_eq!(
+            grid.shortest_path(&ShortestPathType::EndToEnd, false, &Connectivity::Four),
+            Some(4)
+        );
+        assert_eq!(
+            grid.shortest_path(&ShortestPathType::EndToEnd, true, &Connectivity::Four),
+            Some(1)
+        );
+
+        let tall = Grid {
+            width: 2,
+            heights: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            start: 0,
+            goal: 8,
+        };
+        assert_eq!(
+            tall.shortest_path(&ShortestPathType::EndToEnd, false, &Connectivity::Four),
+            Some(4)
+        );
+        assert_eq!(
+            tall.shortest_path(&ShortestPathType::EndToEnd, true, &Connectivity::Four),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_shortest_path_route() {
+        let input = advent_of_code::read_file("examples", 12);
+        let grid = input.parse::<Grid>().unwrap_or_default();
+
+        let route = grid
+            .shortest_path_route(&ShortestPathType::EndToEnd)
+            .unwrap_or_default();
+        assert_eq!(route.len() - 1, 31);
+        assert_eq!(route.first(), Some(&grid.start));
+        assert_eq!(route.last(), Some(&grid.goal));
+        assert!(route.windows(2).all(|step| {
+            let (a, b) = (step[0].min(step[1]), step[0].max(step[1]));
+            (b - a == 1 && a / grid.width == b / grid.width) || b - a == grid.width
+        }));
+
+        let hike = grid
+            .shortest_path_route(&ShortestPathType::Hiking)
+            .unwrap_or_default();
+        assert_eq!(hike.len() - 1, 29);
+        assert_eq!(hike.first().map(|&pos| grid.heights[pos]), Some(0));
+        assert_eq!(hike.last(), Some(&grid.goal));
+    }
+
+    #[test]
+    fn test_shortest_path_astar() {
+        let input = advent_of_code::read_file("examples", 12);
+        let grid = input.parse::<Grid>().unwrap_or_default();
+        assert_eq!(grid.shortest_path_astar(), Some(31));
+        assert_eq!(
+            grid.shortest_path_astar(),
+            grid.shortest_path(&ShortestPathType::EndToEnd, false, &Connectivity::Four)
+        );
+
+        let cliff = Grid {
+            width: 3,
+            heights: vec![0, 9, 1, 0, 9, 1, 0, 0, 1],
+            start: 0,
+            goal: 2,
+        };
+        assert_eq!(cliff.shortest_path_astar(), Some(6));
+        assert_eq!(
+            cliff.shortest_path_astar(),
+            cliff.shortest_path(&ShortestPathType::EndToEnd, false, &Connectivity::Four)
+        );
+    }
+
+    #[test]
+    fn test_shortest_path_eight_directions() {
+        let input = advent_of_code::read_file("examples", 12);
+        let grid = input.parse::<Grid>().unwrap_or_default();
+        for path_type in [ShortestPathType::EndToEnd, ShortestPathType::Hiking] {
+            let four = grid.shortest_path(&path_type, false, &Connectivity::Four);
+            let eight = grid.shortest_path(&path_type, false, &Connectivity::Eight);
+            assert!(eight.is_some());
+            assert!(eight <= four);
+        }
+
+        let open = Grid {
+            width: 3,
+            heights: vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            start: 0,
+            goal: 8,
+        };
+        assert_eq!(
+            open.shortest_path(&ShortestPathType::EndToEnd, false, &Connectivity::Four),
+            Some(4)
+        );
+        assert_eq!(
+            open.shortest_path(&ShortestPathType::EndToEnd, false, &Connectivity::Eight),
+            Some(2)
+        );
+        let mut corner = open.neighbours(2, false, &Connectivity::Eight);
+        corner.sort_unstable();
+        assert_eq!(corner, vec![1, 4, 5]);
+    }
+
+    #[test]
+    fn test_count_shortest_paths() {
+        let grid = Grid {
+            width: 2,
+            heights: vec![0, 0, 0, 0],
+            start: 0,
+            goal: 3,
+        };
+        assert_eq!(grid.count_shortest_paths(&ShortestPathType::EndToEnd), 2);
+    }
 }
@@ -1,88 +1,176 @@
 use std::cmp::Ordering;
+use std::fmt;
 use std::str::FromStr;
 
-#[derive(Clone, Debug, PartialEq)]
-enum Signal {
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Signal {
     Integer(i32),
     List(Vec<Signal>),
 }
 
 impl Signal {
-    fn to_list(&self) -> Vec<Self> {
+    fn as_slice(&self) -> &[Self] {
         match self {
-            Signal::List(l) => l.clone(),
-            Signal::Integer(i) => vec![Signal::Integer(*i)],
+            Signal::List(l) => l,
+            Signal::Integer(_) => std::slice::from_ref(self),
         }
     }
 
     fn new_divider_packet(integer: i32) -> Self {
         Signal::List(vec![Signal::List(vec![Signal::Integer(integer)])])
     }
-}
 
-impl PartialOrd for Signal {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        if let (Self::Integer(a), Self::Integer(b)) = (self, other) {
-            return Some(a.cmp(b));
+    fn compare_logged(&self, other: &Self, depth: usize, log: &mut Vec<String>) -> Ordering {
+        let indent = "  ".repeat(depth);
+        log.push(format!("{indent}- compare {self} vs {other}"));
+
+        match (self, other) {
+            (Self::Integer(a), Self::Integer(b)) => {
+                let ord = a.cmp(b);
+                match ord {
+                    Ordering::Less => {
+                        log.push(format!("{indent}  - left side is smaller: right order"))
+                    }
+                    Ordering::Greater => {
+                        log.push(format!("{indent}  - right side is smaller: wrong order"))
+                    }
+                    Ordering::Equal => (),
+                }
+                return ord;
+            }
+            (Self::Integer(_), Self::List(_)) => log.push(format!(
+                "{indent}  - mixed types; convert left to [{self}] and retry"
+            )),
+            (Self::List(_), Self::Integer(_)) => log.push(format!(
+                "{indent}  - mixed types; convert right to [{other}] and retry"
+            )),
+            (Self::List(_), Self::List(_)) => (),
         }
 
-        let one = self.to_list();
-        let two = other.to_list();
-        let mut ix = 0;
+        let one = self.as_slice();
+        let two = other.as_slice();
 
-        while ix < one.len() && ix < two.len() {
-            let ord = one[ix].partial_cmp(&two[ix]);
-            if ord.unwrap_or(Ordering::Equal) != Ordering::Equal {
+        for (left, right) in one.iter().zip(two.iter()) {
+            let ord = left.compare_logged(right, depth + 1, log);
+            if ord != Ordering::Equal {
                 return ord;
             }
-            ix += 1;
         }
 
-        one.len().partial_cmp(&two.len())
+        let ord = one.len().cmp(&two.len());
+        match ord {
+            Ordering::Less => log.push(format!(
+                "{indent}  - left side ran out of items: right order"
+            )),
+            Ordering::Greater => log.push(format!(
+                "{indent}  - right side ran out of items: wrong order"
+            )),
+            Ordering::Equal => (),
+        }
+        ord
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct ParseSignalError;
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Signal::Integer(i) => write!(f, "{i}"),
+            Signal::List(l) => {
+                write!(f, "[")?;
+                for (ix, item) in l.iter().enumerate() {
+                    if ix > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+#[must_use]
+pub fn compare_verbose(a: &Signal, b: &Signal) -> Vec<String> {
+    let mut log = Vec::new();
+    a.compare_logged(b, 0, &mut log);
+    log
+}
 
 impl Signal {
-    fn parse_list_from_chars(chars: &Vec<char>) -> Result<Self, ParseSignalError> {
-        let mut brackets = 0;
-        let mut pos = 0;
-        let mut child = String::new();
-        let mut children = Vec::new();
-
-        while pos < chars.len() {
-            let ch = chars[pos];
-            if ch == '[' {
-                if brackets > 0 {
-                    child.push(ch);
+    fn packet_cmp(&self, other: &Self) -> Ordering {
+        // lists being compared are kept on an explicit stack of iterators, so deeply
+        // nested signals can't overflow the call stack
+        let mut open = vec![(self.as_slice().iter(), other.as_slice().iter())];
+
+        while let Some((left, right)) = open.last_mut() {
+            match (left.next(), right.next()) {
+                (None, None) => {
+                    open.pop();
                 }
-                brackets += 1;
-            } else if ch == ']' {
-                brackets -= 1;
-                match brackets.cmp(&0) {
-                    Ordering::Less => return Err(ParseSignalError),
-                    Ordering::Equal => {
-                        if !child.is_empty() {
-                            children.push(child.parse::<Signal>()?);
-                            break;
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                (Some(Self::Integer(a)), Some(Self::Integer(b))) => {
+                    let ord = a.cmp(b);
+                    if ord != Ordering::Equal {
+                        return ord;
+                    }
+                }
+                (Some(a), Some(b)) => open.push((a.as_slice().iter(), b.as_slice().iter())),
+            }
+        }
+
+        Ordering::Equal
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseSignalError;
+
+impl Signal {
+    fn parse_list_from_chars(chars: &[char]) -> Result<Self, ParseSignalError> {
+        // lists still being built are kept on an explicit stack, so deeply nested
+        // input can't overflow the call stack
+        let mut open: Vec<Vec<Signal>> = Vec::new();
+        let mut number = String::new();
+        let mut previous = None;
+
+        for (pos, &ch) in chars.iter().enumerate() {
+            match ch {
+                '[' => open.push(Vec::new()),
+                '0'..='9' => number.push(ch),
+                ',' | ']' => {
+                    let list = open.last_mut().ok_or(ParseSignalError)?;
+                    if number.is_empty() {
+                        let after_list = previous == Some(']');
+                        let empty_list = ch == ']' && previous == Some('[');
+                        if !after_list && !empty_list {
+                            return Err(ParseSignalError);
                         }
+                    } else {
+                        list.push(Self::parse_number_from_chars(
+                            &number.chars().collect::<Vec<char>>(),
+                        )?);
+                        number.clear();
                     }
-                    Ordering::Greater => child.push(ch),
-                };
-            } else if ch == ',' && brackets == 1 {
-                children.push(child.parse::<Signal>()?);
-                child = String::new();
-            } else {
-                child.push(ch);
+
+                    if ch == ']' {
+                        let finished = Signal::List(open.pop().unwrap_or_default());
+                        match open.last_mut() {
+                            Some(parent) => parent.push(finished),
+                            None if pos + 1 == chars.len() => return Ok(finished),
+                            None => return Err(ParseSignalError),
+                        }
+                    }
+                }
+                _ => return Err(ParseSignalError),
             }
-            pos += 1;
+            previous = Some(ch);
         }
 
-        Ok(Signal::List(children))
+        Err(ParseSignalError)
     }
 
+    #[allow(clippy::ptr_arg)]
     fn parse_number_from_chars(chars: &Vec<char>) -> Result<Self, ParseSignalError> {
         let number: Result<i32, ParseSignalError> = String::from_iter(chars)
             .parse()
@@ -108,12 +196,12 @@ impl FromStr for Signal {
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, PartialEq)]
 struct SignalPair(Signal, Signal);
 
 impl SignalPair {
     fn is_correctly_ordered(&self) -> bool {
-        self.0 <= self.1
+        self.0.packet_cmp(&self.1) != Ordering::Greater
     }
 }
 
@@ -134,20 +222,21 @@ impl FromStr for SignalPair {
 }
 
 #[must_use]
-pub fn part_one(input: &str) -> Option<u32> {
-    let pairs = parse_input(input);
+pub fn correctly_ordered_indices(input: &str) -> Vec<usize> {
+    parse_input(input)
+        .iter()
+        .enumerate()
+        .filter(|(_, pair)| pair.is_correctly_ordered())
+        .map(|(ix, _)| ix + 1)
+        .collect()
+}
 
+#[must_use]
+pub fn part_one(input: &str) -> Option<u32> {
     Some(
-        pairs
+        correctly_ordered_indices(input)
             .iter()
-            .enumerate()
-            .map(|(ix, pair)| {
-                if pair.is_correctly_ordered() {
-                    u32::try_from(ix + 1).unwrap_or(0)
-                } else {
-                    0
-                }
-            })
+            .map(|ix| u32::try_from(*ix).unwrap_or(0))
             .sum(),
     )
 }
@@ -164,9 +253,9 @@ pub fn part_two(input: &str) -> Option<u32> {
             .iter()
             .flat_map(|p| [p.0.clone(), p.1.clone()])
             .fold((1, 2), |(low, mid), signal| {
-                if signal <= lower_divider {
+                if signal.packet_cmp(&lower_divider) != Ordering::Greater {
                     (low + 1, mid + 1)
-                } else if signal <= upper_divider {
+                } else if signal.packet_cmp(&upper_divider) != Ordering::Greater {
                     (low, mid + 1)
                 } else {
                     (low, mid)
@@ -176,13 +265,22 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(indices.0 * indices.1)
 }
 
+#[must_use]
+pub fn sort_packets(input: &str) -> Vec<Signal> {
+    let mut packets: Vec<Signal> = parse_input(input)
+        .into_iter()
+        .flat_map(|pair| [pair.0, pair.1])
+        .collect();
+    packets.push(Signal::new_divider_packet(2));
+    packets.push(Signal::new_divider_packet(6));
+    packets.sort_by(Signal::packet_cmp);
+    packets
+}
+
 fn parse_input(input: &str) -> Vec<SignalPair> {
     input
         .split("\n\n")
-        .filter_map(|section| match section.parse::<SignalPair>() {
-            Err(_) => None,
-            Ok(pair) => Some(pair),
-        })
+        .filter_map(|section| section.parse::<SignalPair>().ok())
         .collect()
 }
 
@@ -237,6 +335,142 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_signal_invalid() {
+        for input in ["[1,2", "[1,2]]", "[1,,2]", "[,]", "[1]x", "[a]"] {
+            assert_eq!(input.parse::<Signal>(), Err(ParseSignalError), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_parse_signal_deeply_nested() {
+        let depth = 5000;
+        let input = format!("{}7{}", "[".repeat(depth), "]".repeat(depth));
+        let mut signal = input.parse::<Signal>();
+        assert!(signal.is_ok());
+
+        let mut levels = 0;
+        while let Ok(Signal::List(mut children)) = signal {
+            levels += 1;
+            signal = children.pop().ok_or(ParseSignalError);
+        }
+        assert_eq!(levels, depth);
+        assert_eq!(signal, Ok(Signal::Integer(7)));
+    }
+
+    #[test]
+    fn test_part_one_deeply_nested() {
+        let depth = 10000;
+        let left = format!("{}7{}", "[".repeat(depth), "]".repeat(depth));
+        let right = format!("{}8{}", "[".repeat(depth), "]".repeat(depth));
+        assert_eq!(part_one(&format!("{left}\n{right}")), Some(1));
+        assert_eq!(part_one(&format!("{right}\n{left}")), Some(0));
+    }
+
+    #[test]
+    fn test_display_signal_nested() {
+        let signal = "[1,[2,3]]".parse::<Signal>();
+        assert_eq!(signal.map(|s| format!("{s}")), Ok("[1,[2,3]]".to_string()));
+
+        let deeper = "[[1],[2,[3,[4,[5,6,7]]]],8,9]".parse::<Signal>();
+        assert_eq!(
+            deeper.map(|s| s.to_string()),
+            Ok("[[1],[2,[3,[4,[5,6,7]]]],8,9]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_display_signal_empty_lists() {
+        assert_eq!(Signal::List(vec![]).to_string(), "[]");
+        let signal = "[[[]]]".parse::<Signal>();
+        assert_eq!(signal.map(|s| s.to_string()), Ok("[[[]]]".to_string()));
+    }
+
+    #[test]
+    fn test_display_signal_integer() {
+        assert_eq!(Signal::Integer(10).to_string(), "10");
+        let signal = "7".parse::<Signal>();
+        assert_eq!(signal.map(|s| s.to_string()), Ok("7".to_string()));
+    }
+
+    fn verbose(left: &str, right: &str) -> Vec<String> {
+        match (left.parse(), right.parse()) {
+            (Ok(left), Ok(right)) => compare_verbose(&left, &right),
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_compare_verbose_smaller_integer() {
+        assert_eq!(
+            verbose("[1,1,3,1,1]", "[1,1,5,1,1]"),
+            vec![
+                "- compare [1,1,3,1,1] vs [1,1,5,1,1]",
+                "  - compare 1 vs 1",
+                "  - compare 1 vs 1",
+                "  - compare 3 vs 5",
+                "    - left side is smaller: right order",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_verbose_left_runs_out() {
+        let log = verbose("[[4,4],4,4]", "[[4,4],4,4,4]");
+        assert_eq!(
+            log.last(),
+            Some(&"  - left side ran out of items: right order".to_string())
+        );
+    }
+
+    #[test]
+    fn test_compare_verbose_mixed_types() {
+        assert_eq!(
+            verbose("[9]", "[[8,7,6]]"),
+            vec![
+                "- compare [9] vs [[8,7,6]]",
+                "  - compare 9 vs [8,7,6]",
+                "    - mixed types; convert left to [9] and retry",
+                "    - compare 9 vs 8",
+                "      - right side is smaller: wrong order",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_packet_order_is_not_equality() {
+        let integer = Signal::Integer(1);
+        let list = Signal::List(vec![Signal::Integer(1)]);
+        assert_eq!(integer.packet_cmp(&list), Ordering::Equal);
+        assert_ne!(integer, list);
+        assert_ne!(
+            "[[1],2]".parse(),
+            Ok(Signal::List(vec![integer, Signal::Integer(2)]))
+        );
+    }
+
+    #[test]
+    fn test_correctly_ordered_indices() {
+        let input = advent_of_code::read_file("examples", 13);
+        assert_eq!(correctly_ordered_indices(&input), vec![1, 2, 4, 6]);
+    }
+
+    #[test]
+    fn test_sort_packets() {
+        let input = advent_of_code::read_file("examples", 13);
+        let packets = sort_packets(&input);
+        assert_eq!(packets.len(), 18);
+        assert!(packets
+            .windows(2)
+            .all(|pair| pair[0].packet_cmp(&pair[1]) != Ordering::Greater));
+
+        let position =
+            |divider: Signal| packets.iter().position(|p| p == &divider).map(|ix| ix + 1);
+        let lower = position(Signal::new_divider_packet(2));
+        let upper = position(Signal::new_divider_packet(6));
+        assert_eq!(lower.zip(upper).map(|(a, b)| a * b), Some(140));
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 13);
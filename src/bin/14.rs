@@ -1,5 +1,17 @@
-const GRID_COLS: usize = 700;
-const GRID_ROWS: usize = 700;
+const SOURCE_X: usize = 500;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Tile {
+    Empty,
+    Rock,
+    Sand,
+}
+
+#[derive(Clone, Copy)]
+enum SandMode {
+    Abyss,
+    Floor,
+}
 
 struct ParsePointError;
 
@@ -14,106 +26,200 @@ fn read_point(text: &str) -> Result<(usize, usize), ParsePointError> {
     }
 }
 
-fn read_input(input: &str) -> Vec<bool> {
-    let mut rocks = vec![false; GRID_COLS * GRID_ROWS];
-
-    for line in input.lines() {
-        line.split(" -> ")
-            .filter_map(|text| match read_point(text) {
-                Ok(pt) => Some(pt),
-                Err(_) => None,
-            })
-            .reduce(|(ax, ay), (bx, by)| {
-                if ax == bx {
-                    let min_y = ay.min(by);
-                    let max_y = ay.max(by);
-                    for y in min_y..=max_y {
-                        rocks[(y * GRID_COLS) + ax] = true;
-                    }
-                } else if ay == by {
-                    let min_x = ax.min(bx);
-                    let max_x = ax.max(bx);
-                    for x in min_x..=max_x {
-                        rocks[(ay * GRID_COLS) + x] = true;
-                    }
-                }
-                (bx, by)
-            });
-    }
-
-    rocks
+struct Cave {
+    tiles: Vec<Tile>,
+    offset: usize,
+    width: usize,
+    depth: usize,
 }
 
-#[must_use]
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut occupied = read_input(input);
-    let mut rocks = 0;
-    let maximum = {
-        if let Some(last_rock) = occupied.iter().rposition(|v| *v) {
-            let x = last_rock % GRID_COLS;
-            last_rock - x + (GRID_COLS * 2)
+impl Cave {
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        let column = x.checked_sub(self.offset)?;
+        if column < self.width && y < self.height() {
+            Some((y * self.width) + column)
         } else {
-            0
+            None
         }
-    };
+    }
 
-    let mut rock = 500;
-    while rock <= maximum {
-        let down = rock + GRID_COLS;
-        let left = down - 1;
-        let right = down + 1;
+    fn get(&self, x: usize, y: usize) -> Tile {
+        self.index(x, y).map_or(Tile::Empty, |ix| self.tiles[ix])
+    }
 
-        rock = match (occupied[down], occupied[left], occupied[right]) {
-            (true, true, true) => {
-                occupied[rock] = true;
-                rocks += 1;
-                500
+    fn set(&mut self, x: usize, y: usize, tile: Tile) {
+        if let Some(ix) = self.index(x, y) {
+            self.tiles[ix] = tile;
+        }
+    }
+
+    fn height(&self) -> usize {
+        // rows down to the floor, which lies two below the deepest rock
+        self.depth + 2
+    }
+}
+
+fn read_input(input: &str) -> Cave {
+    let paths: Vec<Vec<(usize, usize)>> = input
+        .lines()
+        .map(|line| {
+            line.split(" -> ")
+                .filter_map(|text| read_point(text).ok())
+                .collect()
+        })
+        .collect();
+
+    let depth = paths.iter().flatten().map(|(_, y)| *y).max().unwrap_or(0);
+    // sand on the floor can spread diagonally as far as the floor is deep
+    let spread = depth + 2;
+    let min_x = paths
+        .iter()
+        .flatten()
+        .map(|(x, _)| *x)
+        .chain([SOURCE_X.saturating_sub(spread)])
+        .min()
+        .unwrap_or(0)
+        .saturating_sub(1);
+    let max_x = paths
+        .iter()
+        .flatten()
+        .map(|(x, _)| *x)
+        .chain([SOURCE_X + spread])
+        .max()
+        .unwrap_or(0)
+        + 1;
+
+    let width = max_x - min_x + 1;
+    let mut cave = Cave {
+        tiles: vec![Tile::Empty; width * (depth + 2)],
+        offset: min_x,
+        width,
+        depth,
+    };
+
+    for path in paths {
+        for segment in path.windows(2) {
+            let ((ax, ay), (bx, by)) = (segment[0], segment[1]);
+            if ax == bx {
+                for y in ay.min(by)..=ay.max(by) {
+                    cave.set(ax, y, Tile::Rock);
+                }
+            } else if ay == by {
+                for x in ax.min(bx)..=ax.max(bx) {
+                    cave.set(x, ay, Tile::Rock);
+                }
             }
-            (false, _, _) => down,
-            (true, false, _) => left,
-            (true, true, false) => right,
         }
     }
 
-    Some(rocks)
+    cave
 }
 
-#[must_use]
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut occupied = read_input(input);
-    let mut rocks = 0;
-    let maximum = {
-        if let Some(last_rock) = occupied.iter().rposition(|v| *v) {
-            let x = last_rock % GRID_COLS;
-            last_rock - x + (GRID_COLS * 2)
-        } else {
-            0
+fn reset(grid: &mut [Tile]) {
+    for tile in grid.iter_mut() {
+        if *tile == Tile::Sand {
+            *tile = Tile::Empty;
+        }
+    }
+}
+
+fn simulate(cave: &mut Cave, mode: SandMode) -> Vec<usize> {
+    let mut resting = Vec::new();
+    let blocked = |cave: &Cave, x: Option<usize>, y: usize| match x {
+        None => true,
+        Some(x) => {
+            cave.get(x, y) != Tile::Empty
+                || match mode {
+                    SandMode::Abyss => false,
+                    SandMode::Floor => y >= cave.height(),
+                }
         }
     };
 
-    let mut rock = 500;
-    while !occupied[500] {
-        let down = rock + GRID_COLS;
-        let left = down - 1;
-        let right = down + 1;
+    let (mut x, mut y) = (SOURCE_X, 0);
+    loop {
+        let finished = cave.get(SOURCE_X, 0) != Tile::Empty
+            || match mode {
+                SandMode::Abyss => y > cave.depth,
+                SandMode::Floor => false,
+            };
+        if finished {
+            break;
+        }
 
-        rock = match (
-            occupied[down] || down >= maximum,
-            occupied[left] || left >= maximum,
-            occupied[right] || right >= maximum,
+        let left = x.checked_sub(1);
+        (x, y) = match (
+            blocked(cave, Some(x), y + 1),
+            blocked(cave, left, y + 1),
+            blocked(cave, Some(x + 1), y + 1),
         ) {
             (true, true, true) => {
-                occupied[rock] = true;
-                rocks += 1;
-                500
+                cave.set(x, y, Tile::Sand);
+                resting.extend(cave.index(x, y));
+                (SOURCE_X, 0)
             }
-            (false, _, _) => down,
-            (true, false, _) => left,
-            (true, true, false) => right,
+            (false, _, _) => (x, y + 1),
+            (true, false, _) => (x - 1, y + 1),
+            (true, true, false) => (x + 1, y + 1),
         }
     }
 
-    Some(rocks)
+    resting
+}
+
+#[must_use]
+pub fn sand_resting_positions(input: &str, with_floor: bool) -> Vec<usize> {
+    let mut cave = read_input(input);
+    let mode = if with_floor {
+        SandMode::Floor
+    } else {
+        SandMode::Abyss
+    };
+    simulate(&mut cave, mode)
+}
+
+fn simulate_both(input: &str) -> (Vec<usize>, Vec<usize>) {
+    // the floor run reuses the same cave, so only the sand needs clearing
+    let mut cave = read_input(input);
+    let abyss = simulate(&mut cave, SandMode::Abyss);
+    reset(&mut cave.tiles);
+    let floor = simulate(&mut cave, SandMode::Floor);
+    (abyss, floor)
+}
+
+#[must_use]
+pub fn sand_height_profile(input: &str) -> Vec<usize> {
+    let mut cave = read_input(input);
+    simulate(&mut cave, SandMode::Floor);
+
+    let columns = cave
+        .tiles
+        .iter()
+        .enumerate()
+        .filter(|(_, tile)| **tile == Tile::Sand)
+        .map(|(ix, _)| (ix % cave.width) + cave.offset);
+    let (Some(min_x), Some(max_x)) = (columns.clone().min(), columns.max()) else {
+        return Vec::new();
+    };
+
+    (min_x..=max_x)
+        .map(|x| {
+            (0..cave.height())
+                .find(|&y| cave.get(x, y) == Tile::Sand)
+                .unwrap_or(cave.height())
+        })
+        .collect()
+}
+
+#[must_use]
+pub fn part_one(input: &str) -> Option<u32> {
+    u32::try_from(sand_resting_positions(input, false).len()).ok()
+}
+
+#[must_use]
+pub fn part_two(input: &str) -> Option<u32> {
+    let (_, floor) = simulate_both(input);
+    u32::try_from(floor.len()).ok()
 }
 
 fn main() {
@@ -123,20 +229,80 @@ fn main() {
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
 
     #[test]
     fn test_read_input() {
         let input = advent_of_code::read_file("examples", 14);
-        let rocks = read_input(&input);
+        let cave = read_input(&input);
+
+        assert_eq!(cave.tiles.iter().filter(|x| **x == Tile::Rock).count(), 20);
+        assert_eq!(cave.tiles[0], Tile::Empty);
+        assert_eq!(cave.get(498, 4), Tile::Rock);
+        assert_eq!(cave.get(500, 4), Tile::Empty);
+        assert_eq!(cave.get(502, 4), Tile::Rock);
+        assert_eq!((cave.offset, cave.width, cave.depth), (488, 25, 9));
+        assert_eq!(cave.tiles.len(), 25 * 11);
+    }
+
+    #[test]
+    fn test_reset_between_modes() {
+        let input = advent_of_code::read_file("examples", 14);
+        let mut cave = read_input(&input);
+
+        assert_eq!(simulate(&mut cave, SandMode::Abyss).len(), 24);
+        assert_eq!(cave.tiles.iter().filter(|x| **x == Tile::Sand).count(), 24);
+
+        reset(&mut cave.tiles);
+        assert_eq!(cave.tiles.iter().filter(|x| **x == Tile::Sand).count(), 0);
+        assert_eq!(cave.tiles.iter().filter(|x| **x == Tile::Rock).count(), 20);
+
+        assert_eq!(simulate(&mut cave, SandMode::Floor).len(), 93);
+
+        let (abyss, floor) = simulate_both(&input);
+        assert_eq!(abyss, sand_resting_positions(&input, false));
+        assert_eq!(floor, sand_resting_positions(&input, true));
+    }
+
+    #[test]
+    fn test_rocks_beyond_old_grid() {
+        let input = "495,9 -> 505,9\n900,20 -> 905,20";
+        let cave = read_input(input);
+
+        assert_eq!(cave.get(900, 20), Tile::Rock);
+        assert_eq!(cave.get(905, 20), Tile::Rock);
+        assert_eq!(cave.get(906, 20), Tile::Empty);
+        assert_eq!(cave.depth, 20);
+        assert_eq!(part_one(input), Some(25));
+        assert_eq!(part_two(input), Some(448));
+    }
+
+    #[test]
+    fn test_sand_resting_positions() {
+        let input = advent_of_code::read_file("examples", 14);
+        let cave = read_input(&input);
+
+        let abyss = sand_resting_positions(&input, false);
+        assert_eq!(abyss.len(), 24);
+        assert_eq!(abyss.first(), cave.index(500, 8).as_ref());
+
+        let floor = sand_resting_positions(&input, true);
+        assert_eq!(floor.len(), 93);
+        assert_eq!(floor.first(), abyss.first());
+        assert_eq!(floor.last(), cave.index(500, 0).as_ref());
+    }
+
+    #[test]
+    fn test_sand_height_profile() {
+        let input = advent_of_code::read_file("examples", 14);
+        let profile = sand_height_profile(&input);
 
-        assert_eq!(rocks.iter().map(|x| u32::from(*x)).sum::<u32>(), 20);
-        assert_eq!(rocks[0], false);
-        assert_eq!(rocks[(4 * GRID_COLS) + 498], true);
-        assert_eq!(rocks[(4 * GRID_COLS) + 500], false);
-        assert_eq!(rocks[(4 * GRID_COLS) + 502], true);
+        assert_eq!(profile.len(), 21);
+        assert_eq!(profile[10], 0);
+        assert_eq!(profile.iter().position(|y| *y == 0), Some(10));
+        assert_eq!(profile.first(), Some(&10));
+        assert_eq!(profile.last(), Some(&10));
     }
 
     #[test]
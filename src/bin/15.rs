@@ -2,10 +2,10 @@ use std::collections::HashSet;
 use std::str::FromStr;
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-struct Point(i32, i32);
+pub struct Point(pub i32, pub i32);
 
 #[derive(Debug, PartialEq)]
-struct ParsePointError;
+pub struct ParsePointError;
 
 impl FromStr for Point {
     type Err = ParsePointError;
@@ -44,7 +44,7 @@ impl Point {
 struct Range(i32, i32);
 
 #[derive(Debug, PartialEq)]
-struct Sensor {
+pub struct Sensor {
     location: Point,
     closest_beacon: Point,
     beacon_distance: i32,
@@ -142,37 +142,35 @@ impl Iterator for SensorExteriorPositionIterator {
 fn parse_sensors(input: &str) -> Vec<Sensor> {
     input
         .lines()
-        .filter_map(|line| match line.parse::<Sensor>() {
-            Ok(sensor) => Some(sensor),
-            Err(_) => None,
-        })
+        .filter_map(|line| line.parse::<Sensor>().ok())
         .collect()
 }
 
-fn non_beacon_positions(sensors: &[Sensor], row: i32) -> i32 {
+fn merged_coverage(sensors: &[Sensor], row: i32) -> Vec<Range> {
     let mut ranges: Vec<Range> = sensors
         .iter()
         .map(|s| s.covered_range_for_row(row))
+        .filter(|range| range.1 > range.0)
         .collect();
     ranges.sort();
 
-    let mut x = ranges[0].0;
-    let mut count: i32 = 0;
+    let mut merged: Vec<Range> = Vec::new();
     for range in ranges {
-        // if we already passed this range (fully overlapped by another), skip it
-        if range.1 <= x {
-            continue;
+        match merged.last_mut() {
+            // ranges are half-open, so touching ranges join up with no gap between them
+            Some(last) if range.0 <= last.1 => last.1 = last.1.max(range.1),
+            _ => merged.push(range),
         }
+    }
 
-        // skip any empty space between the previous x position and this range
-        if range.0 > x {
-            x = range.0;
-        }
+    merged
+}
 
-        // add values from the current position up until the end of the range, then move to the end
-        count += range.1 - x;
-        x = range.1;
-    }
+fn non_beacon_positions(sensors: &[Sensor], row: i32) -> i32 {
+    let count: i32 = merged_coverage(sensors, row)
+        .iter()
+        .map(|range| range.1 - range.0)
+        .sum();
 
     let beacons_in_row = {
         let positions: HashSet<i32> = sensors
@@ -191,7 +189,9 @@ fn non_beacon_positions(sensors: &[Sensor], row: i32) -> i32 {
     count - beacons_in_row
 }
 
-fn beacon_position(sensors: &[Sensor], min_coord: i32, max_coord: i32) -> Option<Point> {
+// the original perimeter search, kept as a reference for beacon_position_by_rows
+#[must_use]
+pub fn beacon_position(sensors: &[Sensor], min_coord: i32, max_coord: i32) -> Option<Point> {
     for sensor in sensors {
         for position in sensor
             .positions_just_outside_range()
@@ -208,6 +208,43 @@ fn beacon_position(sensors: &[Sensor], min_coord: i32, max_coord: i32) -> Option
     None
 }
 
+#[must_use]
+pub fn beacon_position_by_rows(sensors: &[Sensor], min: i32, max: i32) -> Option<Point> {
+    for row in min..=max {
+        let mut x = min;
+        for range in merged_coverage(sensors, row) {
+            if range.0 > x {
+                break;
+            }
+            x = x.max(range.1);
+            if x > max {
+                break;
+            }
+        }
+        if x <= max {
+            return Some(Point(x, row));
+        }
+    }
+
+    None
+}
+
+#[must_use]
+pub fn all_beacon_candidates(sensors: &[Sensor], min: i32, max: i32) -> Vec<Point> {
+    let mut candidates = Vec::new();
+
+    for row in min..=max {
+        let mut x = min;
+        for range in merged_coverage(sensors, row) {
+            candidates.extend((x..range.0.min(max + 1)).map(|x| Point(x, row)));
+            x = x.max(range.1);
+        }
+        candidates.extend((x..=max).map(|x| Point(x, row)));
+    }
+
+    candidates
+}
+
 #[must_use]
 pub fn part_one(input: &str) -> Option<i32> {
     Some(non_beacon_positions(&parse_sensors(input), 2_000_000))
@@ -215,7 +252,7 @@ pub fn part_one(input: &str) -> Option<i32> {
 
 #[must_use]
 pub fn part_two(input: &str) -> Option<i64> {
-    let result = beacon_position(&parse_sensors(input), 0, 4_000_000);
+    let result = beacon_position_by_rows(&parse_sensors(input), 0, 4_000_000);
     match result {
         None => None,
         Some(beacon) => {
@@ -258,6 +295,37 @@ mod tests {
         assert_eq!(sensor.covered_range_for_row(16), Range(8, 11),);
     }
 
+    #[test]
+    fn test_merged_coverage_single_sensor() {
+        let sensors = parse_sensors("Sensor at x=0, y=0: closest beacon is at x=2, y=0");
+        assert_eq!(merged_coverage(&sensors, 0), vec![Range(-2, 3)]);
+        assert_eq!(merged_coverage(&sensors, 2), vec![Range(0, 1)]);
+        assert_eq!(merged_coverage(&sensors, 3), vec![]);
+    }
+
+    #[test]
+    fn test_merged_coverage_with_gap() {
+        let sensors = parse_sensors(concat![
+            "Sensor at x=0, y=0: closest beacon is at x=2, y=0\n",
+            "Sensor at x=1, y=1: closest beacon is at x=1, y=2\n",
+            "Sensor at x=10, y=0: closest beacon is at x=12, y=0\n",
+            "Sensor at x=5, y=5: closest beacon is at x=5, y=6\n",
+        ]);
+        assert_eq!(
+            merged_coverage(&sensors, 0),
+            vec![Range(-2, 3), Range(8, 13)]
+        );
+    }
+
+    #[test]
+    fn test_merged_coverage_adjacent() {
+        let sensors = parse_sensors(concat![
+            "Sensor at x=0, y=0: closest beacon is at x=2, y=0\n",
+            "Sensor at x=5, y=0: closest beacon is at x=7, y=0\n",
+        ]);
+        assert_eq!(merged_coverage(&sensors, 0), vec![Range(-2, 8)]);
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 15);
@@ -266,6 +334,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_sensor_exterior_position_iterator() {
         let sensor = Sensor {
             location: Point(0, 0),
@@ -295,4 +364,38 @@ mod tests {
         let sensors = parse_sensors(&input);
         assert_eq!(beacon_position(&sensors, 0, 20), Some(Point(14, 11)));
     }
+
+    #[test]
+    fn test_all_beacon_candidates() {
+        let sensors = parse_sensors(concat![
+            "Sensor at x=1, y=1: closest beacon is at x=1, y=2\n",
+            "Sensor at x=-1, y=0: closest beacon is at x=0, y=0\n",
+            "Sensor at x=3, y=2: closest beacon is at x=2, y=2\n",
+        ]);
+        assert_eq!(
+            all_beacon_candidates(&sensors, 0, 2),
+            vec![Point(2, 0), Point(0, 2)]
+        );
+    }
+
+    #[test]
+    fn test_all_beacon_candidates_example() {
+        let input = advent_of_code::read_file("examples", 15);
+        let sensors = parse_sensors(&input);
+        assert_eq!(all_beacon_candidates(&sensors, 0, 20), vec![Point(14, 11)]);
+    }
+
+    #[test]
+    fn test_beacon_position_by_rows() {
+        let input = advent_of_code::read_file("examples", 15);
+        let sensors = parse_sensors(&input);
+        assert_eq!(
+            beacon_position_by_rows(&sensors, 0, 20),
+            Some(Point(14, 11))
+        );
+        assert_eq!(
+            beacon_position_by_rows(&sensors, 0, 20),
+            beacon_position(&sensors, 0, 20)
+        );
+    }
 }
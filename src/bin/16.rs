@@ -9,7 +9,7 @@ struct ValveInfo {
 }
 
 #[derive(Debug, PartialEq)]
-struct ParseValveSystemError;
+pub struct ParseValveSystemError;
 
 impl FromStr for ValveInfo {
     type Err = ParseValveSystemError;
@@ -37,25 +37,63 @@ impl FromStr for ValveInfo {
     }
 }
 
+// a table entry for every subset of valves is only practical for small inputs
+const MAX_SUBSET_VALVES: usize = 24;
+
 #[derive(Debug, Default)]
-struct ValveSystem {
-    flow_rates: HashMap<i32, i32>,
-    graph: HashMap<i32, HashMap<i32, i32>>,
+pub struct ValveSystem {
+    names: HashMap<u64, String>,
+    flow_rates: HashMap<u64, i32>,
+    graph: HashMap<u64, HashMap<u64, i32>>,
 }
 
 struct ValveSystemWalkState {
     time: i32,
-    position: i32,
-    open_valves: i32,
+    position: u64,
+    open_valves: u64,
     pressure: i32,
 }
 
 impl ValveSystem {
-    fn get_flow_rate(&self, valve_id: i32) -> i32 {
+    fn get_flow_rate(&self, valve_id: u64) -> i32 {
         *self.flow_rates.get(&valve_id).unwrap_or(&0)
     }
 
-    fn best_pressure_possibilities(&self, minutes: i32) -> HashMap<i32, i32> {
+    fn flowing_valve_count(&self) -> usize {
+        self.flow_rates.values().filter(|rate| **rate > 0).count()
+    }
+
+    #[must_use]
+    pub fn distance(&self, from: u64, to: u64) -> Option<i32> {
+        self.graph
+            .get(&from)
+            .and_then(|node| node.get(&to))
+            .copied()
+    }
+
+    #[must_use]
+    pub fn pressure_for_order(&self, order: &[u64], minutes: i32) -> i32 {
+        let mut time = minutes;
+        let mut position = 0;
+        let mut pressure = 0;
+
+        for valve in order {
+            let Some(distance) = self.distance(position, *valve) else {
+                break;
+            };
+            let new_time = time - distance - 1;
+            if new_time < 0 {
+                break;
+            }
+            time = new_time;
+            position = *valve;
+            pressure += self.get_flow_rate(*valve) * time;
+        }
+
+        pressure
+    }
+
+    fn best_pressure_possibilities(&self, minutes: i32) -> HashMap<u64, i32> {
         let mut results = HashMap::new();
         let mut consider = VecDeque::new();
         consider.push_back(ValveSystemWalkState {
@@ -95,6 +133,76 @@ impl ValveSystem {
         results
     }
 
+    #[must_use]
+    pub fn best_route(&self, minutes: i32) -> Vec<(String, i32)> {
+        let mut best: (i32, Vec<(u64, i32)>) = (0, Vec::new());
+        let mut consider = vec![(
+            ValveSystemWalkState {
+                time: minutes,
+                position: 0,
+                open_valves: 0,
+                pressure: 0,
+            },
+            Vec::new(),
+        )];
+
+        while let Some((state, route)) = consider.pop() {
+            if state.pressure > best.0 {
+                best = (state.pressure, route.clone());
+            }
+
+            if let Some(node) = self.graph.get(&state.position) {
+                for (neighbour, distance) in node {
+                    let new_time = state.time - distance - 1;
+                    if state.open_valves & neighbour == 0 && new_time >= 0 {
+                        let mut new_route = route.clone();
+                        new_route.push((*neighbour, minutes - new_time));
+                        consider.push((
+                            ValveSystemWalkState {
+                                time: new_time,
+                                position: *neighbour,
+                                open_valves: state.open_valves | neighbour,
+                                pressure: state.pressure
+                                    + self.get_flow_rate(*neighbour) * new_time,
+                            },
+                            new_route,
+                        ));
+                    }
+                }
+            }
+        }
+
+        best.1
+            .into_iter()
+            .map(|(valve, minute)| {
+                let name = self.names.get(&valve).cloned().unwrap_or_default();
+                (name, minute)
+            })
+            .collect()
+    }
+
+    fn best_pressure_within(&self, possibilities: &HashMap<u64, i32>) -> Option<Vec<i32>> {
+        // best[mask] is the best pressure from opening any subset of the valves in mask
+        let valves = self.flowing_valve_count();
+        if valves > MAX_SUBSET_VALVES {
+            return None;
+        }
+        let mut best = vec![0; 1 << valves];
+        for (open_valves, pressure) in possibilities {
+            if let Ok(ix) = usize::try_from(*open_valves) {
+                best[ix] = best[ix].max(*pressure);
+            }
+        }
+        for mask in 0..best.len() {
+            for bit in 0..valves {
+                if mask & (1 << bit) != 0 {
+                    best[mask] = best[mask].max(best[mask ^ (1 << bit)]);
+                }
+            }
+        }
+        Some(best)
+    }
+
     fn best_pressure_possible(&self, minutes: i32, actors: usize) -> Option<i32> {
         let possibilities = self.best_pressure_possibilities(minutes);
         if actors == 1 {
@@ -116,44 +224,64 @@ impl ValveSystem {
                         .collect::<Vec<i32>>()
                 })
                 .max()
+        } else if actors == 3 {
+            let best = &self.best_pressure_within(&possibilities)?;
+            let all_valves = u64::try_from(best.len() - 1).ok()?;
+            possibilities
+                .iter()
+                .flat_map(|(first_valves, first_pressure)| {
+                    possibilities
+                        .iter()
+                        .filter(move |(second_valves, _)| first_valves & *second_valves == 0)
+                        .filter_map(move |(second_valves, second_pressure)| {
+                            let remaining = all_valves & !(first_valves | second_valves);
+                            let third_pressure = best.get(usize::try_from(remaining).ok()?)?;
+                            Some(first_pressure + second_pressure + third_pressure)
+                        })
+                })
+                .max()
         } else {
             None
         }
     }
 }
 
-impl FromStr for ValveSystem {
-    type Err = ParseValveSystemError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut names: HashMap<String, i32> = HashMap::new();
+impl ValveSystem {
+    /// # Errors
+    ///
+    /// Will return `Err` if any line cannot be parsed as a valve.
+    fn from_str_with_start(s: &str, start: &str) -> Result<Self, ParseValveSystemError> {
+        let mut names: HashMap<String, u64> = HashMap::new();
         let mut flow_rates: HashMap<String, i32> = HashMap::new();
         let mut connections: HashMap<String, HashSet<String>> = HashMap::new();
-        let mut next_valve_no: i32 = 1;
+        let mut flowing_valves: u32 = 0;
 
         for line in s.lines() {
             let valve: ValveInfo = line.parse()?;
-            let number = if valve.name == "AA" {
-                0
+            let number = if valve.name == start {
+                Some(0)
             } else if valve.flow_rate > 0 {
-                let number = next_valve_no;
-                next_valve_no *= 2;
-                number
+                // each flowing valve gets its own bit in the open-valve masks
+                let number = 1_u64
+                    .checked_shl(flowing_valves)
+                    .ok_or(ParseValveSystemError)?;
+                flowing_valves += 1;
+                Some(number)
             } else {
-                -1
+                None
             };
             connections
                 .entry(valve.name.to_string())
                 .or_default()
                 .extend(valve.tunnels);
-            if number == -1 {
+            let Some(number) = number else {
                 continue;
-            }
+            };
             names.insert(valve.name.to_string(), number);
             flow_rates.insert(valve.name.to_string(), valve.flow_rate);
         }
 
-        let mut graph: HashMap<i32, HashMap<i32, i32>> = HashMap::new();
+        let mut graph: HashMap<u64, HashMap<u64, i32>> = HashMap::new();
         for (start_name, start_no) in names.clone() {
             let mut visited = HashSet::new();
             let mut consider = VecDeque::new();
@@ -161,11 +289,11 @@ impl FromStr for ValveSystem {
             while let Some((location, steps)) = consider.pop_front() {
                 visited.insert(location.to_string());
 
-                if location != start_name && location != "AA" {
+                if location != start_name && location != start {
                     if let Some(finish) = names.get(&location) {
                         graph
                             .entry(start_no)
-                            .and_modify(|node: &mut HashMap<i32, i32>| {
+                            .and_modify(|node: &mut HashMap<u64, i32>| {
                                 node.insert(*finish, steps);
                             })
                             .or_insert_with(|| {
@@ -187,12 +315,39 @@ impl FromStr for ValveSystem {
         let flow_rates = flow_rates
             .iter()
             .map(|(name, rate)| (*names.get(name).unwrap_or(&0), *rate))
-            .collect::<HashMap<i32, i32>>();
+            .collect::<HashMap<u64, i32>>();
+        let names = names
+            .into_iter()
+            .map(|(name, number)| (number, name))
+            .collect::<HashMap<u64, String>>();
 
-        Ok(ValveSystem { flow_rates, graph })
+        Ok(ValveSystem {
+            names,
+            flow_rates,
+            graph,
+        })
+    }
+}
+
+impl FromStr for ValveSystem {
+    type Err = ParseValveSystemError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with_start(s, "AA")
     }
 }
 
+#[must_use]
+pub fn best_pressure_including(input: &str, minutes: i32, required: u64) -> Option<i32> {
+    let system: ValveSystem = input.parse().ok()?;
+    system
+        .best_pressure_possibilities(minutes)
+        .iter()
+        .filter(|(open_valves, _)| *open_valves & required == required)
+        .map(|(_, pressure)| *pressure)
+        .max()
+}
+
 #[must_use]
 pub fn part_one(input: &str) -> Option<i32> {
     let system: ValveSystem = input.parse().unwrap_or_default();
@@ -228,6 +383,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_parse_valve_system() {
         let input = advent_of_code::read_file("examples", 16);
 
@@ -256,6 +412,120 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_valve_system_with_start() {
+        let input = advent_of_code::read_file("examples", 16).replace("AA", "START");
+        let system = ValveSystem::from_str_with_start(&input, "START").unwrap_or_default();
+        assert_eq!(system.graph.get(&0).map(HashMap::len), Some(6));
+        assert_eq!(system.best_pressure_possible(30, 1), Some(1651));
+    }
+
+    #[test]
+    fn test_flowing_valve_count() {
+        let input = advent_of_code::read_file("examples", 16);
+        let system: ValveSystem = input.parse().unwrap_or_default();
+        assert_eq!(system.flowing_valve_count(), 6);
+    }
+
+    #[test]
+    fn test_distance() {
+        let input = advent_of_code::read_file("examples", 16);
+        let system: ValveSystem = input.parse().unwrap_or_default();
+        assert_eq!(system.distance(0, 16), Some(5));
+        assert_eq!(system.distance(16, 32), Some(7));
+        assert_eq!(system.distance(4, 0), None);
+        assert_eq!(system.distance(0, 64), None);
+    }
+
+    #[test]
+    fn test_pressure_for_order() {
+        let input = advent_of_code::read_file("examples", 16);
+        let system: ValveSystem = input.parse().unwrap_or_default();
+        assert_eq!(system.pressure_for_order(&[4, 1, 32, 16, 8, 2], 30), 1651);
+        assert_eq!(system.pressure_for_order(&[4], 30), 20 * 28);
+        assert_eq!(system.pressure_for_order(&[16, 4], 6), 0);
+    }
+
+    #[test]
+    fn test_best_pressure_including() {
+        let input = advent_of_code::read_file("examples", 16);
+        let unconstrained = part_one(&input).unwrap_or(0);
+
+        let with_cc = best_pressure_including(&input, 30, 2);
+        assert!(with_cc.is_some_and(|pressure| pressure <= unconstrained));
+        assert_eq!(with_cc, Some(1651));
+
+        let system: ValveSystem = input.parse().unwrap_or_default();
+        let short = best_pressure_including(&input, 10, 2);
+        assert_eq!(short, Some(231));
+        assert!(short < system.best_pressure_possible(10, 1));
+        assert_eq!(best_pressure_including(&input, 30, 64), None);
+    }
+
+    #[test]
+    fn test_best_pressure_three_actors() {
+        let input = advent_of_code::read_file("examples", 16);
+        let system: ValveSystem = input.parse().unwrap_or_default();
+        let two = system.best_pressure_possible(26, 2);
+        let three = system.best_pressure_possible(26, 3);
+        assert!(three >= two);
+        assert_eq!(three, Some(1794));
+        assert_eq!(system.best_pressure_possible(26, 4), None);
+    }
+
+    #[test]
+    fn test_best_route() {
+        let input = advent_of_code::read_file("examples", 16);
+        let system: ValveSystem = input.parse().unwrap_or_default();
+        let flow_rates: HashMap<String, i32> = input
+            .lines()
+            .filter_map(|line| line.parse::<ValveInfo>().ok())
+            .map(|valve| (valve.name, valve.flow_rate))
+            .collect();
+
+        let route = system.best_route(30);
+        assert_eq!(route.first(), Some(&("DD".to_string(), 2)));
+        assert_eq!(
+            route
+                .iter()
+                .map(|(name, minute)| flow_rates.get(name).unwrap_or(&0) * (30 - minute))
+                .sum::<i32>(),
+            1651
+        );
+    }
+
+    #[test]
+    fn test_many_flowing_valves() {
+        // AA leads to V34, then a single corridor runs back down to V00
+        let mut input = "Valve AA has flow rate=0; tunnels lead to valves V34\n".to_string();
+        for number in 0..35 {
+            let flow_rate = if number == 34 { 10 } else { 1 };
+            let mut tunnels: Vec<String> = [number - 1, number + 1]
+                .iter()
+                .filter(|other| (0..35).contains(*other))
+                .map(|other| format!("V{other:02}"))
+                .collect();
+            if number == 34 {
+                tunnels.push("AA".to_string());
+            }
+            input.push_str(&format!(
+                "Valve V{number:02} has flow rate={flow_rate}; tunnels lead to valves {}\n",
+                tunnels.join(", ")
+            ));
+        }
+
+        let system: ValveSystem = input.parse().unwrap_or_default();
+        assert_eq!(system.flowing_valve_count(), 35);
+        assert_eq!(system.distance(0, 1 << 34), Some(1));
+        assert_eq!(system.distance(0, 1 << 33), Some(2));
+        assert_eq!(system.best_pressure_possible(6, 1), Some(42));
+        assert_eq!(
+            system.best_route(6),
+            vec![("V34".to_string(), 2), ("V33".to_string(), 4)]
+        );
+        assert_eq!(system.best_pressure_possible(6, 3), None);
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 16);
@@ -1,10 +1,20 @@
 use std::collections::{HashMap, HashSet};
-use std::iter::repeat;
+use std::iter::repeat_n;
 
-const MAX_X: u64 = 6;
+const DEFAULT_WIDTH: u64 = 7;
+
+// Up to this many rocks a straight simulation is cheap enough that tracking states for cycle
+// detection costs more than it saves; above it the cycle skip is used.
+const BRUTE_FORCE_LIMIT: usize = 5_000;
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-struct Point(u64, u64);
+pub struct Point(pub u64, pub u64);
+
+#[derive(Debug, PartialEq)]
+pub enum InvalidShapesError {
+    Empty,
+    TooWide,
+}
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum Shape {
@@ -15,44 +25,51 @@ enum Shape {
     Square,
 }
 
-fn shape_points(shape_type: Shape, bottom_left: Point) -> Vec<Point> {
-    let Point(x, y) = bottom_left;
-    match shape_type {
-        Shape::Minus => vec![
-            Point(x, y),
-            Point(x + 1, y),
-            Point(x + 2, y),
-            Point(x + 3, y),
-        ],
-        Shape::Plus => vec![
-            Point(x + 1, y),
-            Point(x, y + 1),
-            Point(x + 1, y + 1),
-            Point(x + 2, y + 1),
-            Point(x + 1, y + 2),
-        ],
-        Shape::Angle => vec![
-            Point(x, y),
-            Point(x + 1, y),
-            Point(x + 2, y),
-            Point(x + 2, y + 1),
-            Point(x + 2, y + 2),
-        ],
-        Shape::Pole => vec![
-            Point(x, y),
-            Point(x, y + 1),
-            Point(x, y + 2),
-            Point(x, y + 3),
-        ],
-        Shape::Square => vec![
-            Point(x, y),
-            Point(x + 1, y),
-            Point(x, y + 1),
-            Point(x + 1, y + 1),
-        ],
+impl Shape {
+    fn offsets(self) -> Vec<Point> {
+        match self {
+            Shape::Minus => vec![Point(0, 0), Point(1, 0), Point(2, 0), Point(3, 0)],
+            Shape::Plus => vec![
+                Point(1, 0),
+                Point(0, 1),
+                Point(1, 1),
+                Point(2, 1),
+                Point(1, 2),
+            ],
+            Shape::Angle => vec![
+                Point(0, 0),
+                Point(1, 0),
+                Point(2, 0),
+                Point(2, 1),
+                Point(2, 2),
+            ],
+            Shape::Pole => vec![Point(0, 0), Point(0, 1), Point(0, 2), Point(0, 3)],
+            Shape::Square => vec![Point(0, 0), Point(1, 0), Point(0, 1), Point(1, 1)],
+        }
     }
 }
 
+fn standard_shapes() -> Vec<Vec<Point>> {
+    [
+        Shape::Minus,
+        Shape::Plus,
+        Shape::Angle,
+        Shape::Pole,
+        Shape::Square,
+    ]
+    .into_iter()
+    .map(Shape::offsets)
+    .collect()
+}
+
+fn shape_points(offsets: &[Point], bottom_left: Point) -> Vec<Point> {
+    let Point(x, y) = bottom_left;
+    offsets
+        .iter()
+        .map(|offset| Point(x + offset.0, y + offset.1))
+        .collect()
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum Direction {
     Left,
@@ -79,7 +96,11 @@ struct TetrisCycleState {
 impl TetrisCycleState {
     fn from_game(game: &TetrisGame) -> Self {
         let max_y = game.max_y();
-        let histogram = game.max_y_values.iter().map(|val| max_y - val).collect();
+        let histogram = game
+            .surface_profile()
+            .iter()
+            .map(|val| max_y - val)
+            .collect();
         TetrisCycleState {
             jet_ix: game.jet_ix,
             shape_ix: game.shape_ix % game.shapes.len(),
@@ -103,11 +124,12 @@ enum TetrisCycle {
 }
 
 #[derive(Debug)]
-struct TetrisGame {
+pub struct TetrisGame {
+    width: u64,
     jets: Vec<Direction>,
     jet_ix: usize,
     current_shape: Vec<Point>,
-    shapes: Vec<Shape>,
+    shapes: Vec<Vec<Point>>,
     shape_ix: usize,
     occupied: HashSet<Point>,
     max_y_values: Vec<u64>,
@@ -117,27 +139,63 @@ struct TetrisGame {
 
 impl TetrisGame {
     fn new(input: &str) -> Self {
+        Self::with_width(input, DEFAULT_WIDTH)
+    }
+
+    fn with_width(input: &str, width: u64) -> Self {
+        Self::build(input, width, standard_shapes())
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if there are no shapes or any shape has no points, since such a
+    /// rock could never come to rest, or if any shape is wider than the chamber.
+    pub fn with_shapes(input: &str, shapes: Vec<Vec<Point>>) -> Result<Self, InvalidShapesError> {
+        if shapes.is_empty() || shapes.iter().any(Vec::is_empty) {
+            return Err(InvalidShapesError::Empty);
+        }
+        if shapes.iter().flatten().any(|pt| pt.0 >= DEFAULT_WIDTH) {
+            return Err(InvalidShapesError::TooWide);
+        }
+        Ok(Self::build(input, DEFAULT_WIDTH, shapes))
+    }
+
+    fn build(input: &str, width: u64, shapes: Vec<Vec<Point>>) -> Self {
         let jets = input.trim().chars().map(Direction::from_char).collect();
-        let shapes = vec![
-            Shape::Minus,
-            Shape::Plus,
-            Shape::Angle,
-            Shape::Pole,
-            Shape::Square,
-        ];
-        Self {
+        let mut game = Self {
+            width,
             jets,
             jet_ix: 0,
-            current_shape: shape_points(Shape::Minus, Point(2, 4)),
+            current_shape: Vec::new(),
             shapes,
             shape_ix: 0,
             occupied: HashSet::new(),
-            max_y_values: repeat(0)
-                .take(usize::try_from(MAX_X + 1).unwrap_or(0))
-                .collect(),
+            max_y_values: repeat_n(0, usize::try_from(width).unwrap_or(0)).collect(),
             visited: HashMap::new(),
             cycle: TetrisCycle::None,
-        }
+        };
+        game.current_shape = game.spawn_shape();
+        game
+    }
+
+    fn spawn_shape(&self) -> Vec<Point> {
+        let shape = &self.shapes[self.shape_ix % self.shapes.len()];
+        let points = shape_points(shape, Point(0, self.max_y() + 4));
+        let shape_width = points.iter().map(|pt| pt.0 + 1).max().unwrap_or(0);
+        // rocks start two units from the left wall, unless that would push them into the right
+        let left = 2.min(self.width.saturating_sub(shape_width));
+        points.iter().map(|pt| Point(pt.0 + left, pt.1)).collect()
+    }
+
+    /// Height per column: the highest occupied y in each column, or 0 for an empty column.
+    #[must_use]
+    pub fn surface_profile(&self) -> Vec<u64> {
+        self.max_y_values.clone()
+    }
+
+    #[must_use]
+    pub fn is_occupied(&self, p: Point) -> bool {
+        self.occupied.contains(&p)
     }
 
     fn max_y(&self) -> u64 {
@@ -152,15 +210,16 @@ impl TetrisGame {
 
     fn next_shape(&mut self) {
         for pt in &self.current_shape {
-            let x = usize::try_from(pt.0).unwrap_or(0);
-            self.max_y_values[x] = self.max_y_values[x].max(pt.1);
+            if let Some(max_y) = usize::try_from(pt.0)
+                .ok()
+                .and_then(|x| self.max_y_values.get_mut(x))
+            {
+                *max_y = (*max_y).max(pt.1);
+            }
             self.occupied.insert(*pt);
         }
         self.shape_ix += 1;
-        self.current_shape = shape_points(
-            self.shapes[self.shape_ix % self.shapes.len()],
-            Point(2, self.max_y() + 4),
-        );
+        self.current_shape = self.spawn_shape();
 
         if self.cycle == TetrisCycle::None {
             let state = TetrisCycleState::from_game(self);
@@ -183,7 +242,7 @@ impl TetrisGame {
             .iter()
             .filter_map(|pt| {
                 if (pt.0 == 0 && direction == Direction::Left)
-                    || (pt.0 == MAX_X && direction == Direction::Right)
+                    || (pt.0 + 1 >= self.width && direction == Direction::Right)
                     || (pt.1 == 1 && direction == Direction::Down)
                 {
                     None
@@ -215,6 +274,22 @@ impl TetrisGame {
     }
 
     fn height_after_rocks(&mut self, shapes: usize) -> u64 {
+        if shapes <= BRUTE_FORCE_LIMIT {
+            self.height_brute_force(shapes)
+        } else {
+            self.height_with_cycle_skip(shapes)
+        }
+    }
+
+    fn height_brute_force(&mut self, shapes: usize) -> u64 {
+        self.cycle = TetrisCycle::Removed;
+        while self.shape_ix < shapes {
+            self.tick();
+        }
+        self.max_y()
+    }
+
+    fn height_with_cycle_skip(&mut self, shapes: usize) -> u64 {
         let mut extra_height = 0;
 
         while self.shape_ix < shapes {
@@ -258,7 +333,7 @@ mod tests {
     #[test]
     fn test_shape_minus() {
         assert_eq!(
-            shape_points(Shape::Minus, Point(2, 0)),
+            shape_points(&Shape::Minus.offsets(), Point(2, 0)),
             vec![Point(2, 0), Point(3, 0), Point(4, 0), Point(5, 0),]
         );
     }
@@ -266,7 +341,7 @@ mod tests {
     #[test]
     fn test_shape_plus() {
         assert_eq!(
-            shape_points(Shape::Plus, Point(1, 2)),
+            shape_points(&Shape::Plus.offsets(), Point(1, 2)),
             vec![
                 Point(2, 2),
                 Point(1, 3),
@@ -280,7 +355,7 @@ mod tests {
     #[test]
     fn test_shape_angle() {
         assert_eq!(
-            shape_points(Shape::Angle, Point(3, 4)),
+            shape_points(&Shape::Angle.offsets(), Point(3, 4)),
             vec![
                 Point(3, 4),
                 Point(4, 4),
@@ -294,7 +369,7 @@ mod tests {
     #[test]
     fn test_shape_pole() {
         assert_eq!(
-            shape_points(Shape::Pole, Point(5, 4)),
+            shape_points(&Shape::Pole.offsets(), Point(5, 4)),
             vec![Point(5, 4), Point(5, 5), Point(5, 6), Point(5, 7),]
         );
     }
@@ -302,7 +377,7 @@ mod tests {
     #[test]
     fn test_shape_square() {
         assert_eq!(
-            shape_points(Shape::Square, Point(3, 4)),
+            shape_points(&Shape::Square.offsets(), Point(3, 4)),
             vec![Point(3, 4), Point(4, 4), Point(3, 5), Point(4, 5),]
         );
     }
@@ -320,6 +395,122 @@ mod tests {
         assert_eq!(game.next_jet(), Direction::Right);
     }
 
+    fn first_cycle(input: &str) -> (usize, usize) {
+        let mut game = TetrisGame::new(input);
+        while game.cycle == TetrisCycle::None {
+            game.tick();
+        }
+        match game.cycle {
+            TetrisCycle::Detected(cycle) => {
+                (cycle.second_seen, cycle.second_seen - cycle.first_seen)
+            }
+            _ => (0, 0),
+        }
+    }
+
+    #[test]
+    fn test_cycle_skip_boundary() {
+        let input = advent_of_code::read_file("examples", 17);
+        let (detected_at, cycle_length) = first_cycle(&input);
+        assert!(detected_at > 0);
+        assert!(cycle_length > 0);
+
+        for shapes in [
+            detected_at - 1,
+            detected_at,
+            detected_at + 1,
+            detected_at + cycle_length - 1,
+            detected_at + cycle_length,
+            detected_at + cycle_length + 1,
+        ] {
+            let brute_force = TetrisGame::new(&input).height_brute_force(shapes);
+            let cycle_skip = TetrisGame::new(&input).height_with_cycle_skip(shapes);
+            assert_eq!(cycle_skip, brute_force, "mismatch after {shapes} rocks");
+        }
+    }
+
+    #[test]
+    fn test_brute_force_limit() {
+        let input = advent_of_code::read_file("examples", 17);
+        for shapes in [BRUTE_FORCE_LIMIT, BRUTE_FORCE_LIMIT + 1] {
+            let brute_force = TetrisGame::new(&input).height_brute_force(shapes);
+            let height = TetrisGame::new(&input).height_after_rocks(shapes);
+            assert_eq!(height, brute_force);
+        }
+    }
+
+    #[test]
+    fn test_narrow_chamber() {
+        let input = advent_of_code::read_file("examples", 17);
+        let mut game = TetrisGame::with_width(&input, 4);
+        game.cycle = TetrisCycle::Removed;
+
+        let mut heights = vec![game.max_y()];
+        while game.shape_ix < 100 {
+            let settled = game.shape_ix;
+            game.tick();
+            if game.shape_ix > settled {
+                heights.push(game.max_y());
+            }
+        }
+
+        assert_eq!(heights.len(), 101);
+        assert!(heights.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(heights.last() > heights.first());
+        assert_eq!(game.max_y_values.len(), 4);
+        assert!(game.occupied.iter().all(|pt| pt.0 < 4));
+    }
+
+    #[test]
+    fn test_surface_profile() {
+        let input = advent_of_code::read_file("examples", 17);
+        let mut game = TetrisGame::new(&input);
+        game.height_brute_force(10);
+
+        let profile = game.surface_profile();
+        assert_eq!(profile.len(), 7);
+        assert_eq!(profile.iter().max(), Some(&17));
+        assert!(game.is_occupied(Point(2, 1)));
+        assert!(game.is_occupied(Point(5, 1)));
+        assert!(!game.is_occupied(Point(1, 1)));
+    }
+
+    #[test]
+    fn test_custom_single_square() {
+        let input = advent_of_code::read_file("examples", 17);
+        let single = || TetrisGame::with_shapes(&input, vec![vec![Point(0, 0)]]);
+
+        assert_eq!(
+            single().map(|game| game.current_shape),
+            Ok(vec![Point(2, 4)])
+        );
+        let settled = single().map(|mut game| (game.height_brute_force(70), game.occupied.len()));
+        assert!(matches!(settled, Ok((10..=70, 70))));
+
+        assert_eq!(
+            single().map(|mut game| game.height_with_cycle_skip(10_000)),
+            single().map(|mut game| game.height_brute_force(10_000))
+        );
+    }
+
+    #[test]
+    fn test_custom_shapes_invalid() {
+        let input = advent_of_code::read_file("examples", 17);
+        let error = |shapes| TetrisGame::with_shapes(&input, shapes).err();
+
+        assert_eq!(error(Vec::new()), Some(InvalidShapesError::Empty));
+        assert_eq!(error(vec![Vec::new()]), Some(InvalidShapesError::Empty));
+        assert_eq!(
+            error(vec![vec![Point(0, 0)], Vec::new()]),
+            Some(InvalidShapesError::Empty)
+        );
+        assert_eq!(
+            error(vec![vec![Point(0, 0), Point(7, 0)]]),
+            Some(InvalidShapesError::TooWide)
+        );
+        assert_eq!(error(vec![vec![Point(6, 0)]]), None);
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 17);
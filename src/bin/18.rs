@@ -5,6 +5,23 @@ use std::str::FromStr;
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 struct Cube(i32, i32, i32);
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Connectivity {
+    Six,
+    Eighteen,
+    TwentySix,
+}
+
+impl Connectivity {
+    fn max_axes_changed(self) -> i32 {
+        match self {
+            Connectivity::Six => 1,
+            Connectivity::Eighteen => 2,
+            Connectivity::TwentySix => 3,
+        }
+    }
+}
+
 impl Cube {
     fn neighbours(self) -> HashSet<Cube> {
         let mut neighbours = HashSet::new();
@@ -17,13 +34,25 @@ impl Cube {
         neighbours
     }
 
-    fn within_bounds(&self, min_coord: i32, max_coord: i32) -> bool {
-        self.0 >= min_coord
-            && self.0 <= max_coord
-            && self.1 >= min_coord
-            && self.1 <= max_coord
-            && self.2 >= min_coord
-            && self.2 <= max_coord
+    fn air_neighbours(self, connectivity: Connectivity) -> HashSet<Cube> {
+        let mut neighbours = HashSet::new();
+        for dx in -1_i32..=1 {
+            for dy in -1_i32..=1 {
+                for dz in -1_i32..=1 {
+                    let axes_changed = dx.abs() + dy.abs() + dz.abs();
+                    if axes_changed > 0 && axes_changed <= connectivity.max_axes_changed() {
+                        neighbours.insert(self + Cube(dx, dy, dz));
+                    }
+                }
+            }
+        }
+        neighbours
+    }
+
+    fn within_bounds(&self, min: Cube, max: Cube) -> bool {
+        (min.0..=max.0).contains(&self.0)
+            && (min.1..=max.1).contains(&self.1)
+            && (min.2..=max.2).contains(&self.2)
     }
 }
 
@@ -61,69 +90,109 @@ fn surface_area(cubes: &HashSet<Cube>) -> u32 {
         .sum()
 }
 
-fn bounds(cubes: &HashSet<Cube>) -> (i32, i32) {
+fn bounding_box(cubes: &HashSet<Cube>) -> (Cube, Cube) {
     let (min, max) = cubes
         .iter()
-        .map(|cube| {
+        .map(|cube| (*cube, *cube))
+        .reduce(|(min, max), (cube, _)| {
             (
-                cube.0.min(cube.1).min(cube.2),
-                cube.0.max(cube.1).max(cube.2),
+                Cube(min.0.min(cube.0), min.1.min(cube.1), min.2.min(cube.2)),
+                Cube(max.0.max(cube.0), max.1.max(cube.1), max.2.max(cube.2)),
             )
         })
-        .fold((0, 0), |acc, cube| (acc.0.min(cube.0), acc.1.max(cube.1)));
-    (min - 1, max + 1)
+        .unwrap_or((Cube(0, 0, 0), Cube(0, 0, 0)));
+    (min + Cube(-1, -1, -1), max + Cube(1, 1, 1))
 }
 
-fn external_surface_area(cubes: &HashSet<Cube>) -> u32 {
-    let (min_coord, max_coord) = bounds(cubes);
+fn outside_air(cubes: &HashSet<Cube>, connectivity: Connectivity) -> HashSet<Cube> {
+    let (min, max) = bounding_box(cubes);
 
-    let mut area = 0;
     let mut visited = HashSet::new();
     let mut consider = VecDeque::new();
-    consider.push_back(Cube(min_coord, min_coord, min_coord));
+    consider.push_back(min);
 
     while let Some(location) = consider.pop_front() {
         if visited.contains(&location) {
             continue;
         }
 
-        if cubes.contains(&location) {
-            area += 1;
-        } else {
-            visited.insert(location);
-            for neighbour in location.neighbours() {
-                if neighbour.within_bounds(min_coord, max_coord) {
-                    consider.push_back(neighbour);
+        visited.insert(location);
+        for neighbour in location.air_neighbours(connectivity) {
+            if !cubes.contains(&neighbour) && neighbour.within_bounds(min, max) {
+                consider.push_back(neighbour);
+            }
+        }
+    }
+
+    visited
+}
+
+fn external_surface_area(cubes: &HashSet<Cube>, connectivity: Connectivity) -> u32 {
+    outside_air(cubes, connectivity)
+        .iter()
+        .map(|location| {
+            location
+                .neighbours()
+                .intersection(cubes)
+                .fold(0, |a, _b| a + 1)
+        })
+        .sum()
+}
+
+fn trapped_air_cells(cubes: &HashSet<Cube>) -> HashSet<Cube> {
+    let (min, max) = bounding_box(cubes);
+    let outside = outside_air(cubes, Connectivity::Six);
+
+    let mut trapped = HashSet::new();
+    for x in min.0..=max.0 {
+        for y in min.1..=max.1 {
+            for z in min.2..=max.2 {
+                let cell = Cube(x, y, z);
+                if !cubes.contains(&cell) && !outside.contains(&cell) {
+                    trapped.insert(cell);
                 }
             }
         }
     }
 
-    area
+    trapped
+}
+
+fn pocket_surface_area(cubes: &HashSet<Cube>) -> u32 {
+    trapped_air_cells(cubes)
+        .iter()
+        .map(|cell| cell.neighbours().intersection(cubes).fold(0, |a, _b| a + 1))
+        .sum()
+}
+
+fn parse_cubes(input: &str) -> Option<HashSet<Cube>> {
+    input
+        .lines()
+        .map(|line| line.parse::<Cube>().ok())
+        .collect()
+}
+
+#[must_use]
+pub fn surface_areas(input: &str) -> Option<(u32, u32, u32)> {
+    let cubes = parse_cubes(input)?;
+    let total = surface_area(&cubes);
+    let external = external_surface_area(&cubes, Connectivity::Six);
+    Some((total, external, pocket_surface_area(&cubes)))
 }
 
 #[must_use]
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut cubes = HashSet::new();
-    for line in input.lines() {
-        match line.parse::<Cube>() {
-            Err(_) => return None,
-            Ok(cube) => cubes.insert(cube),
-        };
-    }
-    Some(surface_area(&cubes))
+    parse_cubes(input).map(|cubes| surface_area(&cubes))
+}
+
+#[must_use]
+pub fn exterior_surface_area(input: &str, connectivity: Connectivity) -> Option<u32> {
+    parse_cubes(input).map(|cubes| external_surface_area(&cubes, connectivity))
 }
 
 #[must_use]
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut cubes = HashSet::new();
-    for line in input.lines() {
-        match line.parse::<Cube>() {
-            Err(_) => return None,
-            Ok(cube) => cubes.insert(cube),
-        };
-    }
-    Some(external_surface_area(&cubes))
+    exterior_surface_area(input, Connectivity::Six)
 }
 
 fn main() {
@@ -143,6 +212,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_cube_neighbours() {
         let cube = Cube(1, 2, 3);
         let neighbours = cube.neighbours();
@@ -152,6 +222,89 @@ mod tests {
         assert_eq!(neighbours.contains(&Cube(1, 2, 5)), false);
     }
 
+    #[test]
+    fn test_air_neighbours() {
+        let cube = Cube(1, 2, 3);
+        assert_eq!(cube.air_neighbours(Connectivity::Six), cube.neighbours());
+        assert_eq!(cube.air_neighbours(Connectivity::Eighteen).len(), 18);
+        assert_eq!(cube.air_neighbours(Connectivity::TwentySix).len(), 26);
+    }
+
+    #[test]
+    fn test_external_surface_area_connectivity() {
+        let cubes: HashSet<Cube> = [
+            Cube(0, 1, 1),
+            Cube(2, 1, 1),
+            Cube(1, 0, 1),
+            Cube(1, 2, 1),
+            Cube(1, 1, 0),
+            Cube(1, 1, 2),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(surface_area(&cubes), 36);
+        assert_eq!(external_surface_area(&cubes, Connectivity::Six), 30);
+        assert_eq!(external_surface_area(&cubes, Connectivity::Eighteen), 36);
+        assert_eq!(external_surface_area(&cubes, Connectivity::TwentySix), 36);
+
+        let input = "0,1,1\n2,1,1\n1,0,1\n1,2,1\n1,1,0\n1,1,2";
+        assert_eq!(exterior_surface_area(input, Connectivity::Six), Some(30));
+        assert_eq!(
+            exterior_surface_area(input, Connectivity::Eighteen),
+            Some(36)
+        );
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let input = advent_of_code::read_file("examples", 18);
+        let cubes = parse_cubes(&input).unwrap_or_default();
+        assert_eq!(bounding_box(&cubes), (Cube(0, 0, 0), Cube(4, 4, 7)));
+
+        let cubes: HashSet<Cube> = [Cube(5, -2, 10), Cube(7, 3, 10)].into_iter().collect();
+        assert_eq!(bounding_box(&cubes), (Cube(4, -3, 9), Cube(8, 4, 11)));
+    }
+
+    #[test]
+    fn test_surface_areas() {
+        let input = advent_of_code::read_file("examples", 18);
+        assert_eq!(surface_areas(&input), Some((64, 58, 6)));
+    }
+
+    #[test]
+    fn test_trapped_air_cells() {
+        let input = advent_of_code::read_file("examples", 18);
+        let cubes = parse_cubes(&input).unwrap_or_default();
+        let trapped = trapped_air_cells(&cubes);
+        assert_eq!(trapped, [Cube(2, 2, 5)].into_iter().collect());
+
+        assert_eq!(
+            pocket_surface_area(&cubes),
+            surface_area(&cubes) - external_surface_area(&cubes, Connectivity::Six)
+        );
+    }
+
+    #[test]
+    fn test_long_hollow_droplet() {
+        // a closed 21x3x3 tube, far from the origin, with a hollow core along its length
+        let mut cubes = HashSet::new();
+        for x in 0..=20 {
+            for y in 50..=52 {
+                for z in 50..=52 {
+                    let core = (1..=19).contains(&x) && y == 51 && z == 51;
+                    if !core {
+                        cubes.insert(Cube(x, y, z));
+                    }
+                }
+            }
+        }
+
+        assert_eq!(bounding_box(&cubes), (Cube(-1, 49, 49), Cube(21, 53, 53)));
+        assert_eq!(surface_area(&cubes), 348);
+        assert_eq!(external_surface_area(&cubes, Connectivity::Six), 270);
+        assert_eq!(trapped_air_cells(&cubes).len(), 19);
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 18);
@@ -1,5 +1,7 @@
 use std::collections::{HashSet, VecDeque};
 use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 struct Cost(u32, u32, u32);
@@ -126,7 +128,7 @@ impl Blueprint {
             // DFS
             best = best.max(state.open_geodes);
 
-            if state.maximum_achievable_open_geodes() < best {
+            if state.maximum_achievable_open_geodes(self) < best {
                 continue;
             }
 
@@ -135,6 +137,10 @@ impl Blueprint {
 
         best
     }
+
+    fn quality_level(&self, minutes: u32) -> u32 {
+        self.number * self.most_geodes_openable(minutes)
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -159,7 +165,7 @@ impl Robot {
 const ROBOT_TYPES: [Robot; 4] = [Robot::Ore, Robot::Clay, Robot::Obsidian, Robot::Geode];
 
 fn div_ceil(a: u32, b: u32) -> u32 {
-    (a + b - 1) / b
+    a.div_ceil(b)
 }
 
 #[derive(Debug, Eq, Hash, PartialEq)]
@@ -188,8 +194,22 @@ impl State {
         }
     }
 
-    fn maximum_achievable_open_geodes(&self) -> u32 {
-        self.open_geodes + ((self.time * (self.time - 1)) / 2)
+    fn maximum_achievable_open_geodes(&self, blueprint: &Blueprint) -> u32 {
+        // optimistically assume ore and clay are unlimited, so an obsidian robot can be built
+        // every minute alongside a geode robot whenever there's enough obsidian for one
+        let geode_cost = blueprint.geode_robot_cost.2;
+        let mut geodes = self.open_geodes;
+        let mut obsidian = self.obsidian;
+
+        for (obsidian_robots, time) in (self.obsidian_robots..).zip((1..=self.time).rev()) {
+            if obsidian >= geode_cost {
+                obsidian -= geode_cost;
+                geodes += time - 1;
+            }
+            obsidian += obsidian_robots;
+        }
+
+        geodes
     }
 
     fn possible_moves(&self, blueprint: &Blueprint, most_robots_needed: Cost) -> HashSet<State> {
@@ -259,34 +279,90 @@ impl State {
     }
 }
 
+fn join_wrapped_blueprints(input: &str) -> Vec<String> {
+    let mut blueprints: Vec<String> = Vec::new();
+
+    for line in input.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        match blueprints.last_mut() {
+            Some(current) if !line.starts_with("Blueprint") => {
+                current.push(' ');
+                current.push_str(line);
+            }
+            _ => blueprints.push(line.to_string()),
+        }
+    }
+
+    blueprints
+}
+
+fn read_blueprints(input: &str) -> Vec<Blueprint> {
+    join_wrapped_blueprints(input)
+        .iter()
+        .filter_map(|text| text.parse::<Blueprint>().ok())
+        .collect()
+}
+
+fn in_parallel<'a, I, T, F>(blueprints: I, evaluate: F) -> Vec<T>
+where
+    I: IntoIterator<Item = &'a Blueprint>,
+    T: Send,
+    F: Fn(&Blueprint) -> T + Sync,
+{
+    let evaluate = &evaluate;
+    thread::scope(|scope| {
+        let handles: Vec<_> = blueprints
+            .into_iter()
+            .map(|blueprint| scope.spawn(move || evaluate(blueprint)))
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|err| std::panic::resume_unwind(err))
+            })
+            .collect()
+    })
+}
+
+#[must_use]
+pub fn evaluate_timed(input: &str, minutes: u32) -> Vec<(u32, u32, Duration)> {
+    in_parallel(&read_blueprints(input), |blueprint| {
+        let timer = Instant::now();
+        let geodes = blueprint.most_geodes_openable(minutes);
+        (blueprint.number, geodes, timer.elapsed())
+    })
+}
+
+/// Evaluates every blueprint numbered up to `up_to` (or all of them when `None`),
+/// regardless of the order they were listed in.
+fn evaluate(blueprints: &[Blueprint], minutes: u32, up_to: Option<u32>) -> Vec<(u32, u32)> {
+    let selected = blueprints
+        .iter()
+        .filter(|blueprint| up_to.is_none_or(|limit| blueprint.number <= limit));
+    in_parallel(selected, |blueprint| {
+        (blueprint.number, blueprint.most_geodes_openable(minutes))
+    })
+}
+
 #[must_use]
 pub fn part_one(input: &str) -> Option<u32> {
     Some(
-        input
-            .lines()
-            .filter_map(|line| match line.parse::<Blueprint>() {
-                Err(_) => None,
-                Ok(blueprint) => Some(blueprint.number * blueprint.most_geodes_openable(24)),
-            })
-            .sum(),
+        in_parallel(&read_blueprints(input), |blueprint| {
+            blueprint.quality_level(24)
+        })
+        .iter()
+        .sum(),
     )
 }
 
 #[must_use]
 pub fn part_two(input: &str) -> Option<u32> {
     Some(
-        input
-            .lines()
-            .filter_map(|line| match line.parse::<Blueprint>() {
-                Err(_) => None,
-                Ok(blueprint) => {
-                    if blueprint.number <= 3 {
-                        Some(blueprint.most_geodes_openable(32))
-                    } else {
-                        None
-                    }
-                }
-            })
+        evaluate(&read_blueprints(input), 32, Some(3))
+            .iter()
+            .map(|(_, geodes)| geodes)
             .product(),
     )
 }
@@ -321,6 +397,20 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_read_wrapped_blueprints() {
+        let input = advent_of_code::read_file("examples", 19);
+        let wrapped = concat!(
+            "Blueprint 1:\n",
+            "  Each ore robot costs 4 ore. Each clay robot costs 2 ore.\n",
+            "  Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.\n",
+            "\n",
+            "Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.\n",
+        );
+        assert_eq!(read_blueprints(wrapped), read_blueprints(&input));
+        assert_eq!(read_blueprints(wrapped).len(), 2);
+    }
+
     #[test]
     fn test_most_robots_needed() {
         let blueprint = Blueprint {
@@ -334,6 +424,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_possible_moves_initial() {
         let blueprint = Blueprint {
             number: 1,
@@ -380,6 +471,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_possible_moves_example_path() {
         let blueprint = Blueprint {
             number: 1,
@@ -536,6 +628,106 @@ mod tests {
         assert_eq!(blueprint.most_geodes_openable(24), 9);
     }
 
+    fn states_explored(
+        blueprint: &Blueprint,
+        minutes: u32,
+        bound: impl Fn(&State) -> u32,
+    ) -> (u32, usize) {
+        let most_robots_needed = blueprint.most_robots_needed();
+        let mut best = 0;
+        let mut explored = 0;
+        let mut consider = VecDeque::new();
+        consider.push_front(State::create_initial(minutes));
+
+        while let Some(state) = consider.pop_front() {
+            explored += 1;
+            best = best.max(state.open_geodes);
+            if bound(&state) < best {
+                continue;
+            }
+            consider.extend(state.possible_moves(blueprint, most_robots_needed));
+        }
+
+        (best, explored)
+    }
+
+    #[test]
+    fn test_tighter_bound_explores_fewer_states() {
+        let input = advent_of_code::read_file("examples", 19);
+        for blueprint in read_blueprints(&input) {
+            let loose = states_explored(&blueprint, 24, |state| {
+                state.open_geodes + ((state.time * (state.time - 1)) / 2)
+            });
+            let tight = states_explored(&blueprint, 24, |state| {
+                state.maximum_achievable_open_geodes(&blueprint)
+            });
+            assert_eq!(tight.0, loose.0);
+            assert_eq!(tight.0, blueprint.most_geodes_openable(24));
+            assert!(tight.1 < loose.1);
+        }
+    }
+
+    #[test]
+    fn test_quality_level() {
+        let input = advent_of_code::read_file("examples", 19);
+        let blueprints = read_blueprints(&input);
+        assert_eq!(blueprints[0].quality_level(24), 9);
+        assert_eq!(blueprints[1].quality_level(24), 24);
+    }
+
+    #[test]
+    fn test_evaluate_timed() {
+        let input = advent_of_code::read_file("examples", 19);
+        let blueprints = read_blueprints(&input);
+        let results = evaluate_timed(&input, 24);
+
+        assert_eq!(results.len(), 2);
+        for (blueprint, (number, geodes, elapsed)) in blueprints.iter().zip(results) {
+            assert_eq!(number, blueprint.number);
+            assert_eq!(geodes, blueprint.most_geodes_openable(24));
+            assert!(elapsed > Duration::ZERO);
+        }
+    }
+
+    #[test]
+    fn test_in_parallel_matches_sequential() {
+        let input = advent_of_code::read_file("examples", 19);
+        let blueprints = read_blueprints(&input);
+        let sequential: Vec<u32> = blueprints
+            .iter()
+            .map(|blueprint| blueprint.quality_level(24))
+            .collect();
+        let parallel = in_parallel(&blueprints, |blueprint| blueprint.quality_level(24));
+        assert_eq!(parallel, sequential);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let input = advent_of_code::read_file("examples", 19);
+        let blueprints = read_blueprints(&input);
+
+        let full = evaluate(&blueprints, 24, None);
+        assert_eq!(full, vec![(1, 9), (2, 12)]);
+
+        let short = evaluate(&blueprints, 16, None);
+        assert_eq!(short.len(), 2);
+        for ((number, geodes), (_, full_geodes)) in short.iter().zip(&full) {
+            assert!(geodes < full_geodes, "blueprint {number}");
+        }
+
+        assert_eq!(evaluate(&blueprints, 24, Some(1)), vec![(1, 9)]);
+        assert_eq!(evaluate(&blueprints, 24, Some(5)), full);
+        assert_eq!(evaluate(&blueprints, 24, Some(0)), Vec::new());
+    }
+
+    #[test]
+    fn test_evaluate_selects_by_number() {
+        let input = advent_of_code::read_file("examples", 19);
+        let mut blueprints = read_blueprints(&input);
+        blueprints.reverse();
+        assert_eq!(evaluate(&blueprints, 24, Some(1)), vec![(1, 9)]);
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 19);
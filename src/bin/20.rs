@@ -38,8 +38,13 @@ fn mix(list: &[i64], rounds: usize) -> Vec<i64> {
     circle.iter().map(|(_i, v)| **v).collect()
 }
 
+#[must_use]
+pub fn position_of(list: &[i64], value: i64) -> Option<usize> {
+    list.iter().position(|item| *item == value)
+}
+
 fn grove_coordinates(list: &[i64]) -> i64 {
-    let zero = list.iter().position(|value| *value == 0).unwrap_or(0);
+    let zero = position_of(list, 0).unwrap_or(0);
     [1000, 2000, 3000]
         .iter()
         .map(|ix| list[(zero + ix) % list.len()])
@@ -84,6 +89,15 @@ mod tests {
         assert_eq!(mix(&list, 1), vec![0, 3, -2, 1, 2, -3, 4]);
     }
 
+    #[test]
+    fn test_position_of() {
+        let mixed = mix(&[1, 2, -3, 3, -2, 0, 4], 1);
+        assert_eq!(position_of(&mixed, 4), Some(6));
+        assert_eq!(position_of(&mixed, 0), Some(0));
+        assert_eq!(position_of(&mixed, 7), None);
+        assert_eq!(position_of(&[5, 1, 5], 5), Some(0));
+    }
+
     #[test]
     fn test_grove_coordinates() {
         let list = vec![3, -2, 1, 2, -3, 4, 0];
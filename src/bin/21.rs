@@ -1,4 +1,4 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq)]
@@ -102,6 +102,30 @@ fn parse_monkeys(input: &str) -> HashMap<String, Monkey> {
     monkeys
 }
 
+fn dependents_of(monkeys: &HashMap<String, Monkey>, name: &str) -> HashSet<String> {
+    let mut parents: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (parent, monkey) in monkeys {
+        if let Monkey::Calculation(a, _, b) = monkey {
+            parents.entry(a).or_default().push(parent);
+            parents.entry(b).or_default().push(parent);
+        }
+    }
+
+    let mut dependents = HashSet::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(name);
+
+    while let Some(current) = queue.pop_front() {
+        for parent in parents.get(current).into_iter().flatten() {
+            if dependents.insert((*parent).to_string()) {
+                queue.push_back(parent);
+            }
+        }
+    }
+
+    dependents
+}
+
 #[must_use]
 pub fn part_one(input: &str) -> Option<i64> {
     let monkeys = parse_monkeys(input);
@@ -111,6 +135,7 @@ pub fn part_one(input: &str) -> Option<i64> {
 #[must_use]
 pub fn part_two(input: &str) -> Option<i64> {
     let mut monkeys = parse_monkeys(input);
+    let humn_dependents = dependents_of(&monkeys, "humn");
 
     if let Some(Monkey::Calculation(a, _, b)) = monkeys.get("root") {
         let mut queue = VecDeque::new();
@@ -128,8 +153,12 @@ pub fn part_two(input: &str) -> Option<i64> {
             let monkey = monkeys.get(name).unwrap_or(&Monkey::Value(0));
             if let Monkey::Calculation(a, _, b) = monkey {
                 if let Some((expect_a, expect_b)) = monkey.inputs_to_get_value(&monkeys, expected) {
-                    queue.push_back((a, expect_a));
-                    queue.push_back((b, expect_b));
+                    // only the side which depends on humn can still be changed
+                    for (side, expect) in [(a, expect_a), (b, expect_b)] {
+                        if side == "humn" || humn_dependents.contains(side) {
+                            queue.push_back((side, expect));
+                        }
+                    }
                 }
             }
         }
@@ -211,6 +240,25 @@ mod tests {
         assert_eq!(val.inputs_to_get_value(&monkeys, 18), None);
     }
 
+    #[test]
+    fn test_dependents_of() {
+        let input = advent_of_code::read_file("examples", 21);
+        let monkeys = parse_monkeys(&input);
+        let dependents = dependents_of(&monkeys, "humn");
+
+        assert_eq!(
+            dependents,
+            ["ptdq", "lgvd", "cczh", "pppw", "root"]
+                .iter()
+                .map(|name| (*name).to_string())
+                .collect()
+        );
+        assert!(dependents.contains("root"));
+        assert!(!dependents.contains("sjmn"));
+        assert!(!dependents.contains("humn"));
+        assert!(dependents_of(&monkeys, "root").is_empty());
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 21);
@@ -4,6 +4,9 @@ use std::ops::Add;
 #[derive(Debug, PartialEq)]
 struct ParseInputError;
 
+#[derive(Debug, PartialEq)]
+struct NoStartingPositionError;
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 struct Position(usize, usize);
 
@@ -44,7 +47,7 @@ impl Tile {
 
 #[derive(Debug, PartialEq)]
 enum Instruction {
-    Forward(u32),
+    Forward(i32),
     TurnLeft,
     TurnRight,
 }
@@ -65,10 +68,10 @@ impl InstructionCollector {
 
     fn push_current(&mut self) {
         if !self.current.is_empty() {
-            if let Ok(steps) = self.current.parse::<u32>() {
+            if let Ok(steps) = self.current.parse::<i32>() {
                 self.collected.push(Instruction::Forward(steps));
-                self.current.clear();
             }
+            self.current.clear();
         }
     }
 
@@ -82,6 +85,11 @@ impl InstructionCollector {
                 self.push_current();
                 self.collected.push(Instruction::TurnRight);
             }
+            '-' => {
+                // a minus sign always starts a new count
+                self.push_current();
+                self.current.push(c);
+            }
             _ => {
                 if c.is_numeric() {
                     self.current.push(c);
@@ -98,15 +106,12 @@ struct Square {
 }
 
 impl Square {
-    fn first_open_position(&self) -> Position {
-        let x = {
-            if let Some(row) = self.tiles.first() {
-                row.iter().position(|tile| tile == &Tile::Open).unwrap_or(0)
-            } else {
-                0
-            }
-        };
-        Position(x, 0)
+    fn first_open_position(&self) -> Option<Position> {
+        self.tiles.iter().enumerate().find_map(|(y, row)| {
+            row.iter()
+                .position(|tile| tile == &Tile::Open)
+                .map(|x| Position(x, y))
+        })
     }
 
     fn is_position_open(&self, position: Position) -> bool {
@@ -639,19 +644,34 @@ impl GroveMap {
         }
     }
 
-    fn create_initial_position(&self) -> CubePosition {
-        let position = {
-            if let Some(square) = self.squares.first() {
-                square.first_open_position()
-            } else {
-                Position(0, 0)
-            }
-        };
-        CubePosition {
-            square: 0,
-            position,
-            facing: Direction::Right,
-        }
+    fn cube_position_from_flat(&self, flat: Position, facing: Direction) -> Option<CubePosition> {
+        let square_position = Position(flat.0 / self.square_size, flat.1 / self.square_size);
+        let square = self
+            .squares
+            .iter()
+            .position(|square| square.position == square_position)?;
+        Some(CubePosition {
+            square,
+            position: Position(flat.0 % self.square_size, flat.1 % self.square_size),
+            facing,
+        })
+    }
+
+    fn create_initial_position(&self) -> Result<CubePosition, NoStartingPositionError> {
+        // the start is described on the flat map, so locate it there first
+        let flat = self
+            .squares
+            .iter()
+            .find_map(|square| {
+                let open = square.first_open_position()?;
+                Some(Position(
+                    (square.position.0 * self.square_size) + open.0,
+                    (square.position.1 * self.square_size) + open.1,
+                ))
+            })
+            .ok_or(NoStartingPositionError)?;
+        self.cube_position_from_flat(flat, Direction::Right)
+            .ok_or(NoStartingPositionError)
     }
 
     fn position_after_instruction(
@@ -668,47 +688,78 @@ impl GroveMap {
                 facing: cube_pos.facing.turn_right(),
                 ..cube_pos
             },
-            Instruction::Forward(steps) => {
-                let mut cube_pos = cube_pos;
-                for _ in 0..*steps {
-                    let ahead = cube_pos.position_ahead(self);
-                    if let Some(square) = self.squares.get(ahead.square) {
-                        if square.is_position_open(ahead.position) {
-                            cube_pos = ahead;
-                        } else {
-                            break;
-                        }
-                    } else {
-                        break;
-                    }
+            Instruction::Forward(steps) if *steps < 0 => {
+                let reversed = CubePosition {
+                    facing: cube_pos.facing.reverse(),
+                    ..cube_pos
+                };
+                let moved = self.position_after_steps(reversed, steps.unsigned_abs());
+                CubePosition {
+                    facing: moved.facing.reverse(),
+                    ..moved
                 }
+            }
+            Instruction::Forward(steps) => {
+                self.position_after_steps(cube_pos, steps.unsigned_abs())
+            }
+        }
+    }
 
-                cube_pos
+    fn position_after_steps(&self, cube_pos: CubePosition, steps: u32) -> CubePosition {
+        let mut cube_pos = cube_pos;
+        for _ in 0..steps {
+            let ahead = cube_pos.position_ahead(self);
+            if let Some(square) = self.squares.get(ahead.square) {
+                if square.is_position_open(ahead.position) {
+                    cube_pos = ahead;
+                } else {
+                    break;
+                }
+            } else {
+                break;
             }
         }
+
+        cube_pos
     }
 
-    fn follow_instructions(&self, instructions: &Vec<Instruction>) -> CubePosition {
-        let mut position = self.create_initial_position();
+    fn follow_instructions(
+        &self,
+        instructions: &Vec<Instruction>,
+    ) -> Result<CubePosition, NoStartingPositionError> {
+        let mut position = self.create_initial_position()?;
 
         for instruction in instructions {
             position = self.position_after_instruction(position, instruction);
         }
 
-        position
+        Ok(position)
     }
 }
 
+fn is_instruction_line(text: &str) -> bool {
+    let text = text.trim();
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == 'L' || c == 'R' || c == '-')
+}
+
 fn parse_input(
     input: &str,
     assemble_cube: bool,
 ) -> Result<(GroveMap, Vec<Instruction>), ParseInputError> {
     let parts: Vec<&str> = input.split("\n\n").collect();
     if parts.len() == 2 {
-        let map = GroveMap::from_input(parts[0], assemble_cube);
+        let (map_part, instruction_part) = if is_instruction_line(parts[0]) {
+            (parts[1], parts[0])
+        } else {
+            (parts[0], parts[1])
+        };
+        let map = GroveMap::from_input(map_part, assemble_cube);
 
         let mut collector = InstructionCollector::new();
-        for c in parts[1].chars() {
+        for c in instruction_part.chars() {
             collector.push_char(c);
         }
         collector.push_current();
@@ -722,7 +773,9 @@ fn parse_input(
 #[must_use]
 pub fn part_one(input: &str) -> Option<u32> {
     if let Ok((map, instructions)) = parse_input(input, false) {
-        Some(map.follow_instructions(&instructions).password(&map))
+        map.follow_instructions(&instructions)
+            .ok()
+            .map(|position| position.password(&map))
     } else {
         None
     }
@@ -731,7 +784,9 @@ pub fn part_one(input: &str) -> Option<u32> {
 #[must_use]
 pub fn part_two(input: &str) -> Option<u32> {
     if let Ok((map, instructions)) = parse_input(input, true) {
-        Some(map.follow_instructions(&instructions).password(&map))
+        map.follow_instructions(&instructions)
+            .ok()
+            .map(|position| position.password(&map))
     } else {
         None
     }
@@ -1004,6 +1059,18 @@ mod tests {
         assert_eq!(parse_input(&input, false), expected);
     }
 
+    #[test]
+    fn test_parse_input_instructions_first() {
+        let input = advent_of_code::read_file("examples", 22);
+        let parts: Vec<&str> = input.split("\n\n").collect();
+        let swapped = format!("{}\n\n{}\n", parts[1].trim(), parts[0]);
+
+        assert!(is_instruction_line(parts[1]));
+        assert!(!is_instruction_line(parts[0]));
+        assert_eq!(parse_input(&swapped, false), parse_input(&input, false));
+        assert_eq!(parse_input(&swapped, true), parse_input(&input, true));
+    }
+
     #[test]
     fn test_wrap_around() {
         let map = example_grove_map(false);
@@ -1022,6 +1089,112 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_initial_position_skips_walled_top_row() {
+        let map = GroveMap::from_input("###\n#.#\n...", false);
+        assert_eq!(
+            map.create_initial_position(),
+            Ok(CubePosition {
+                square: 0,
+                position: Position(1, 1),
+                facing: Direction::Right,
+            })
+        );
+    }
+
+    #[test]
+    fn test_initial_position_all_walls() {
+        let map = GroveMap::from_input("##\n##", false);
+        assert_eq!(map.create_initial_position(), Err(NoStartingPositionError));
+    }
+
+    #[test]
+    fn test_move_backwards() {
+        let map = example_grove_map(false);
+        let cube_pos = CubePosition {
+            square: 4,
+            position: Position(0, 3),
+            facing: Direction::Down,
+        };
+        assert_eq!(
+            map.position_after_instruction(cube_pos, &Instruction::Forward(-3)),
+            CubePosition {
+                square: 4,
+                position: Position(0, 0),
+                facing: Direction::Down,
+            }
+        );
+    }
+
+    #[test]
+    fn test_cube_position_from_flat() {
+        let map = example_grove_map(true);
+        let cube_pos = CubePosition {
+            square: 3,
+            position: Position(2, 1),
+            facing: Direction::Left,
+        };
+        let flat = cube_pos.to_flat_position(&map);
+        assert_eq!(flat, Position(10, 5));
+        assert_eq!(
+            map.cube_position_from_flat(flat, Direction::Left),
+            Some(cube_pos)
+        );
+        assert_eq!(
+            map.cube_position_from_flat(Position(0, 0), Direction::Right),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_minus_after_digits() {
+        let mut collector = InstructionCollector::new();
+        for c in "5-3L-".chars() {
+            collector.push_char(c);
+        }
+        collector.push_current();
+        assert_eq!(
+            collector.collected,
+            vec![
+                Instruction::Forward(5),
+                Instruction::Forward(-3),
+                Instruction::TurnLeft,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_move_backwards_extreme_count() {
+        // the tile behind is a wall, so the walker stays put rather than overflowing
+        let map = GroveMap::from_input("..\n#.", false);
+        let cube_pos = CubePosition {
+            square: 0,
+            position: Position(0, 0),
+            facing: Direction::Up,
+        };
+        assert_eq!(
+            map.position_after_instruction(cube_pos, &Instruction::Forward(i32::MIN)),
+            cube_pos
+        );
+    }
+
+    #[test]
+    fn test_parse_backwards_instruction() {
+        let mut collector = InstructionCollector::new();
+        for c in "-3R2".chars() {
+            collector.push_char(c);
+        }
+        collector.push_current();
+        assert_eq!(
+            collector.collected,
+            vec![
+                Instruction::Forward(-3),
+                Instruction::TurnRight,
+                Instruction::Forward(2),
+            ]
+        );
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 22);
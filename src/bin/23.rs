@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::str::FromStr;
 
 const GRID_SIZE: usize = 400;
@@ -61,6 +61,7 @@ struct State {
     rounds: usize,
 }
 
+#[derive(Debug, PartialEq)]
 struct ParseStateError;
 
 impl FromStr for State {
@@ -94,6 +95,14 @@ impl State {
         cycle
     }
 
+    fn elf_positions(&self) -> HashSet<usize> {
+        self.grid
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, is_elf)| if *is_elf { Some(pos) } else { None })
+            .collect()
+    }
+
     fn enclosed_empty_spaces(&self) -> usize {
         let mut elves = 0;
         let (mut left, mut right, mut top, mut bottom) =
@@ -128,7 +137,7 @@ impl State {
                 if occupied != 0 {
                     if let Some(dest) = checks.iter().find_map(|dir| moved_pos(pos, dir, occupied))
                     {
-                        proposed.entry(dest).or_insert_with(Vec::new).push(pos);
+                        proposed.entry(dest).or_default().push(pos);
                     }
                 }
             }
@@ -168,9 +177,22 @@ impl State {
 }
 
 #[must_use]
-pub fn part_one(input: &str) -> Option<usize> {
+pub fn run_rounds_recording(input: &str, rounds: usize) -> Vec<HashSet<usize>> {
+    let mut snapshots = Vec::new();
+    if let Ok(mut state) = input.parse::<State>() {
+        snapshots.push(state.elf_positions());
+        while state.rounds < rounds {
+            state.next_round();
+            snapshots.push(state.elf_positions());
+        }
+    }
+    snapshots
+}
+
+#[must_use]
+pub fn spread_for_rounds(input: &str, rounds: usize) -> Option<usize> {
     if let Ok(mut state) = input.parse::<State>() {
-        while state.rounds < 10 {
+        while state.rounds < rounds {
             state.next_round();
         }
         Some(state.enclosed_empty_spaces())
@@ -179,6 +201,11 @@ pub fn part_one(input: &str) -> Option<usize> {
     }
 }
 
+#[must_use]
+pub fn part_one(input: &str) -> Option<usize> {
+    spread_for_rounds(input, 10)
+}
+
 #[must_use]
 pub fn part_two(input: &str) -> Option<usize> {
     if let Ok(mut state) = input.parse::<State>() {
@@ -201,6 +228,28 @@ fn main() {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_run_rounds_recording() {
+        let input = advent_of_code::read_file("examples", 23);
+        let snapshots = run_rounds_recording(&input, 10);
+        let initial = input.parse::<State>().map(|state| state.elf_positions());
+
+        assert_eq!(snapshots.len(), 11);
+        assert_eq!(Ok(&snapshots[0]), initial.as_ref());
+        assert!(snapshots
+            .iter()
+            .all(|snapshot| snapshot.len() == snapshots[0].len()));
+        assert_ne!(snapshots[0], snapshots[1]);
+    }
+
+    #[test]
+    fn test_spread_for_rounds() {
+        let input = advent_of_code::read_file("examples", 23);
+        assert_eq!(spread_for_rounds(&input, 0), Some(27));
+        assert_eq!(spread_for_rounds(&input, 5), Some(99));
+        assert_eq!(spread_for_rounds(&input, 10), Some(110));
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 23);
@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq)]
-struct State {
+pub struct State {
     width: usize,
     height: usize,
     time: u32,
@@ -11,7 +11,7 @@ struct State {
 }
 
 #[derive(Debug, PartialEq)]
-struct ParseStateError;
+pub struct ParseStateError;
 
 const WALL: u32 = 1;
 const BLIZZARD_U: u32 = 2;
@@ -66,6 +66,21 @@ impl FromStr for State {
 }
 
 impl State {
+    #[must_use]
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    #[must_use]
+    pub fn is_wall(&self, pos: (usize, usize)) -> bool {
+        let (x, y) = pos;
+        if x >= self.width + 2 || y >= self.height + 2 {
+            return true;
+        }
+        let pos = ((y + 1) * (self.width + 2)) + x;
+        self.obstacles.get(pos).is_none_or(|ob| ob & WALL == WALL)
+    }
+
     fn advance(&mut self) {
         self.time += 1;
         let total_width = self.width + 2;
@@ -150,17 +165,27 @@ impl State {
 }
 
 #[must_use]
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn fastest_from_minute(input: &str, start_minute: u32) -> Option<u32> {
     if let Ok(mut state) = input.parse::<State>() {
+        state.clear_elf_positions();
+        while state.time < start_minute {
+            state.advance();
+        }
+        state.reset_for_trip(0);
         while !state.is_solved() {
             state.advance();
         }
-        Some(state.time)
+        Some(state.time - start_minute)
     } else {
         None
     }
 }
 
+#[must_use]
+pub fn part_one(input: &str) -> Option<u32> {
+    fastest_from_minute(input, 0)
+}
+
 #[must_use]
 pub fn part_two(input: &str) -> Option<u32> {
     if let Ok(mut state) = input.parse::<State>() {
@@ -263,6 +288,31 @@ mod tests {
         assert_eq!(initial, one,);
     }
 
+    #[test]
+    fn test_dimensions_and_walls() {
+        let input = advent_of_code::read_file("examples", 24);
+        let state = input.parse::<State>();
+        let wall = |pos| state.as_ref().map(|state| state.is_wall(pos));
+
+        assert_eq!(state.as_ref().map(State::dimensions), Ok((6, 4)));
+        assert_eq!(wall((0, 0)), Ok(true));
+        assert_eq!(wall((7, 0)), Ok(true));
+        assert_eq!(wall((0, 5)), Ok(true));
+        assert_eq!(wall((7, 5)), Ok(true));
+        assert_eq!(wall((1, 0)), Ok(false));
+        assert_eq!(wall((6, 5)), Ok(false));
+        assert_eq!(wall((1, 1)), Ok(false));
+        assert_eq!(wall((8, 1)), Ok(true));
+    }
+
+    #[test]
+    fn test_fastest_from_minute() {
+        let input = advent_of_code::read_file("examples", 24);
+        assert_eq!(fastest_from_minute(&input, 0), part_one(&input));
+        assert_eq!(fastest_from_minute(&input, 12), Some(18));
+        assert_eq!(fastest_from_minute(&input, 18), Some(12));
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 24);
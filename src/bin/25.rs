@@ -1,48 +1,105 @@
 const SNAFU_BASE: i64 = 5;
+const SNAFU_DIGITS: [char; 5] = ['=', '-', '0', '1', '2'];
 
-fn snafu_to_decimal(snafu: &str) -> i64 {
-    snafu
+/// Balanced digits only exist for odd bases of three or more, so any other base
+/// gives `None`.
+#[must_use]
+pub fn to_balanced(decimal: i64, base: i64) -> Option<Vec<i64>> {
+    if base < 3 || base % 2 == 0 {
+        return None;
+    }
+
+    let half = base / 2;
+    let mut digits = Vec::new();
+    let mut remaining = decimal;
+
+    loop {
+        let digit = (remaining + half).rem_euclid(base) - half;
+        digits.push(digit);
+        remaining = (remaining - digit) / base;
+        if remaining == 0 {
+            break;
+        }
+    }
+
+    digits.reverse();
+    Some(digits)
+}
+
+#[must_use]
+pub fn from_balanced(digits: &[i64], base: i64) -> i64 {
+    digits.iter().fold(0, |acc, digit| (acc * base) + digit)
+}
+
+#[must_use]
+pub fn snafu_to_decimal(snafu: &str) -> i64 {
+    let digits: Vec<i64> = snafu
         .chars()
-        .rev()
-        .enumerate()
-        .filter_map(|(pos, digit)| {
-            if let Ok(pos) = u32::try_from(pos) {
-                let base = SNAFU_BASE.pow(pos);
-                match digit {
-                    '2' => Some(2),
-                    '1' => Some(1),
-                    '0' => Some(0),
-                    '-' => Some(-1),
-                    '=' => Some(-2),
-                    _ => None,
-                }
-                .map(|qty| base * qty)
-            } else {
-                None
-            }
+        .map(|digit| {
+            SNAFU_DIGITS
+                .iter()
+                .position(|glyph| *glyph == digit)
+                .and_then(|ix| i64::try_from(ix).ok())
+                .map_or(0, |ix| ix - SNAFU_BASE / 2)
         })
-        .sum()
+        .collect();
+    from_balanced(&digits, SNAFU_BASE)
 }
 
-fn decimal_to_snafu(decimal: i64) -> String {
-    let mut snafu = String::new();
-    let mut remaining = decimal;
+#[must_use]
+pub fn decimal_to_snafu(decimal: i64) -> String {
+    to_balanced(decimal, SNAFU_BASE)
+        .unwrap_or_default()
+        .iter()
+        .map(|digit| {
+            let ix = usize::try_from(digit + SNAFU_BASE / 2).unwrap_or(0);
+            SNAFU_DIGITS[ix]
+        })
+        .collect()
+}
+
+fn snafu_digit_value(glyph: char) -> i32 {
+    SNAFU_DIGITS
+        .iter()
+        .position(|digit| *digit == glyph)
+        .and_then(|ix| i32::try_from(ix).ok())
+        .map_or(0, |ix| ix - 2)
+}
+
+#[must_use]
+pub fn snafu_add(a: &str, b: &str) -> String {
+    let mut a_digits = a.chars().rev().map(snafu_digit_value);
+    let mut b_digits = b.chars().rev().map(snafu_digit_value);
+    let mut carry = 0;
+    let mut glyphs = Vec::new();
 
     loop {
-        let ix = usize::try_from((remaining + 2) % 5).unwrap_or(0);
-        snafu.push(['=', '-', '0', '1', '2'][ix]);
-        remaining = (remaining + 2) / 5;
-        if remaining == 0 {
+        let (a_digit, b_digit) = (a_digits.next(), b_digits.next());
+        if a_digit.is_none() && b_digit.is_none() && carry == 0 {
             break;
         }
+        let total = a_digit.unwrap_or(0) + b_digit.unwrap_or(0) + carry;
+        let digit = (total + 2).rem_euclid(5) - 2;
+        carry = (total - digit) / 5;
+        glyphs.push(SNAFU_DIGITS[usize::try_from(digit + 2).unwrap_or(0)]);
     }
 
-    snafu.chars().rev().collect()
+    while glyphs.len() > 1 && glyphs.last() == Some(&'0') {
+        glyphs.pop();
+    }
+    if glyphs.is_empty() {
+        glyphs.push('0');
+    }
+    glyphs.iter().rev().collect()
 }
 
 #[must_use]
 pub fn part_one(input: &str) -> Option<String> {
-    Some(decimal_to_snafu(input.lines().map(snafu_to_decimal).sum()))
+    Some(
+        input
+            .lines()
+            .fold(String::from("0"), |total, line| snafu_add(&total, line)),
+    )
 }
 
 fn main() {
@@ -55,6 +112,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::inconsistent_digit_grouping)]
     fn test_snafu_to_decimal() {
         assert_eq!(snafu_to_decimal("1"), 1);
         assert_eq!(snafu_to_decimal("2"), 2);
@@ -83,6 +141,54 @@ mod tests {
         assert_eq!(decimal_to_snafu(2022), "1=11-2".to_string());
     }
 
+    #[test]
+    fn test_to_balanced() {
+        assert_eq!(to_balanced(0, 3), Some(vec![0]));
+        assert_eq!(to_balanced(1, 3), Some(vec![1]));
+        assert_eq!(to_balanced(2, 3), Some(vec![1, -1]));
+        assert_eq!(to_balanced(5, 3), Some(vec![1, -1, -1]));
+        assert_eq!(to_balanced(-4, 3), Some(vec![-1, -1]));
+        assert_eq!(to_balanced(2022, 5), Some(vec![1, -2, 1, 1, -1, 2]));
+    }
+
+    #[test]
+    fn test_to_balanced_rejects_invalid_bases() {
+        for base in [-3, 0, 1, 2, 4, 10] {
+            assert_eq!(to_balanced(7, base), None, "base {base}");
+        }
+    }
+
+    #[test]
+    fn test_from_balanced() {
+        assert_eq!(from_balanced(&[1, -1], 3), 2);
+        assert_eq!(from_balanced(&[1, -1, -1], 3), 5);
+        assert_eq!(from_balanced(&[-1, -1], 3), -4);
+        assert_eq!(from_balanced(&[1, -2, 1, 1, -1, 2], 5), 2022);
+    }
+
+    #[test]
+    fn test_snafu_add() {
+        assert_eq!(snafu_add("1=", "1-"), "12".to_string());
+        assert_eq!(snafu_add("1", "1"), "2".to_string());
+        assert_eq!(snafu_add("", ""), "0".to_string());
+        assert_eq!(snafu_add("1", "-"), "0".to_string());
+        assert_eq!(snafu_add("1=11-2", "0"), "1=11-2".to_string());
+    }
+
+    #[test]
+    fn test_snafu_add_carries() {
+        assert_eq!(snafu_add("2", "2"), "1-".to_string());
+        assert_eq!(snafu_add("2222", "2222"), decimal_to_snafu(624));
+        assert_eq!(snafu_add("2222", "1"), "1====".to_string());
+        assert_eq!(snafu_add("====", "-"), "-2222".to_string());
+
+        let input = advent_of_code::read_file("examples", 25);
+        let total = input
+            .lines()
+            .fold(String::from("0"), |total, line| snafu_add(&total, line));
+        assert_eq!(total, "2=-1=0".to_string());
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 25);